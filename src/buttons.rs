@@ -11,7 +11,8 @@ pub struct Button {
     last_pressed: Option<SystemTime>,
     last_ticked: Option<SystemTime>,
     pub is_hold: bool,
-    has_been_pressed: bool
+    has_been_pressed: bool,
+    has_been_tapped: bool
 }
 
 impl Button {
@@ -23,7 +24,8 @@ impl Button {
             last_pressed: None,
             last_ticked: None,
             is_hold: false,
-            has_been_pressed: false
+            has_been_pressed: false,
+            has_been_tapped: false
         }
     }
 
@@ -34,32 +36,30 @@ impl Button {
             if self.last_state == Level::Low {
                 self.last_pressed = Some(SystemTime::now());
                 self.has_been_pressed = true;
+                self.has_been_tapped = false;
                 self.is_hold = false;
             }
             self.last_state = Level::High;
-            match self.last_pressed {
-                Some(t) => {
-                    if SystemTime::now().duration_since(t).unwrap().as_millis() >= HOLD_DURATION {
-                        self.last_ticked = Some(SystemTime::now());
-                        self.has_been_pressed = true;
-                        self.is_hold = true;
-                    } else {
-                        self.last_ticked = None;
-                    }
+            if let Some(t) = self.last_pressed {
+                if SystemTime::now().duration_since(t).unwrap().as_millis() >= HOLD_DURATION {
+                    self.last_ticked = Some(SystemTime::now());
+                    self.has_been_pressed = true;
+                    self.is_hold = true;
+                } else {
+                    self.last_ticked = None;
                 }
-                None => {}
-            };
-            match self.last_ticked {
-                Some(t) => {
-                    if SystemTime::now().duration_since(t).unwrap().as_millis() >= TICK_DURATION {
-                        self.last_ticked = Some(SystemTime::now());
-                        self.has_been_pressed = true;
-                        self.is_hold = true;
-                    }
+            }
+            if let Some(t) = self.last_ticked {
+                if SystemTime::now().duration_since(t).unwrap().as_millis() >= TICK_DURATION {
+                    self.last_ticked = Some(SystemTime::now());
+                    self.has_been_pressed = true;
+                    self.is_hold = true;
                 }
-                None => { }
             }
         } else if self.pin.is_high() {
+            if self.last_state == Level::High && !self.is_hold {
+                self.has_been_tapped = true;
+            }
             self.last_state = Level::Low;
             self.last_ticked = None;
             self.last_pressed = None;
@@ -71,14 +71,51 @@ impl Button {
     pub fn was_pressed(&mut self) -> bool {
         if self.has_been_pressed {
             self.has_been_pressed = false;
-            return true;
+            true
         } else {
-            return false;
+            false
         }
     }
 
     /// Whether the button is being held.
     pub fn is_held(&self) -> bool { self.is_hold }
+
+    /// Return whether the button has been released without being held.
+    pub fn was_tapped(&mut self) -> bool {
+        if self.has_been_tapped {
+            self.has_been_tapped = false;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// The GPIO pin (BCM numbering) of each button in a `ButtonSet`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ButtonPins {
+    pub a: u8,
+    pub b: u8,
+    pub c: u8,
+    pub up: u8,
+    pub down: u8,
+    pub left: u8,
+    pub right: u8,
+}
+
+impl Default for ButtonPins {
+    /// The pins used by the Adafruit 128x64 OLED Bonnet.
+    fn default() -> ButtonPins {
+        ButtonPins {
+            a: 5,
+            b: 6,
+            c: 4,
+            up: 17,
+            down: 22,
+            left: 27,
+            right: 23
+        }
+    }
 }
 
 pub struct ButtonSet {
@@ -116,20 +153,24 @@ impl ButtonSet {
 
     pub fn get_button(n: u8) -> Button {
         let gpio = Gpio::new().expect("Could not init board");
-        return Button::new(gpio.get(n)
+        Button::new(gpio.get(n)
             .expect("Could not get pin")
-            .into_input_pullup());
+            .into_input_pullup())
     }
 
     pub fn default_pins() -> ButtonSet {
+        ButtonSet::with_pins(&ButtonPins::default())
+    }
+
+    pub fn with_pins(pins: &ButtonPins) -> ButtonSet {
         ButtonSet {
-            a: ButtonSet::get_button(5),
-            b: ButtonSet::get_button(6),
-            c: ButtonSet::get_button(4),
-            up: ButtonSet::get_button(17),
-            down: ButtonSet::get_button(22),
-            left: ButtonSet::get_button(27),
-            right: ButtonSet::get_button(23)
+            a: ButtonSet::get_button(pins.a),
+            b: ButtonSet::get_button(pins.b),
+            c: ButtonSet::get_button(pins.c),
+            up: ButtonSet::get_button(pins.up),
+            down: ButtonSet::get_button(pins.down),
+            left: ButtonSet::get_button(pins.left),
+            right: ButtonSet::get_button(pins.right)
         }
     }
 
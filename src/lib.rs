@@ -1,32 +1,50 @@
 mod buttons;
 mod view;
+mod navigation;
+mod watcher;
+mod runtime;
+mod toast;
+mod notification;
 pub mod views;
 
 pub use buttons::*;
 pub use view::*;
+pub use navigation::*;
+pub use runtime::*;
+pub use toast::*;
+pub use notification::*;
 
 pub use views::ON;
 pub use views::OFF;
 
 /// Create a new menu.
+///
+/// An entry may be given a third element, a closure returning the value shown
+/// on the right side of its row.
 /// ```
+/// # #[macro_use] extern crate piscreen;
+/// # use piscreen::views::*;
+/// # fn main() {
 /// menu_view![
-///     ("Entry Name 2", text_view!("Text view with value!")),
+///     ("Entry Name 1", text_view!("Text view with value!")),
 ///     ("Entry Name 2", menu_view![
 ///         ("Submenu!", text_view!("Text view inside submenu!"))
 ///     ]),
-///     ("Entry Name 2", menu_view!("Submenu!")),
+///     ("Volume", EmptyView::new(), || format!("{}%", 70)),
 ///     // Can also manually create view entries:
-///     ("Entry Name 3", TextView::new("Hello, world!".to_owned())
+///     ("Entry Name 3", TextView::new("Hello, world!"))
 /// ];
+/// # }
 /// ```
 #[macro_export]
 macro_rules! menu_view {
-    ( $( ($x:expr, $y:expr) ),* ) => {
+    ( $( ($x:expr, $y:expr $(, $v:expr)?) ),* ) => {
         {
-            let mut temp_menu = MenuView::new();
+            let mut temp_menu = $crate::views::MenuView::new();
             $(
-                temp_menu.add_entry(($x.to_owned(), Box::new($y)));
+                let entry = $crate::views::MenuEntry::new(&$x.to_owned(), Box::new($y));
+                $( let entry = entry.with_value($v); )?
+                temp_menu.add_entry(entry);
             )*
             temp_menu
         }
@@ -35,18 +53,38 @@ macro_rules! menu_view {
 
 /// Create a new text view.
 /// ```
+/// # #[macro_use] extern crate piscreen;
+/// # fn main() {
 /// text_view!("Hello, world!");
+/// # }
 /// ```
 #[macro_export]
 macro_rules! text_view {
-    ( $x:expr ) => { { TextView::new($x) } }
+    ( $x:expr ) => { { $crate::views::TextView::new($x) } }
 }
 
 /// Create a new file view from a path string.
 /// ```
+/// # #[macro_use] extern crate piscreen;
+/// # fn main() {
 /// file_view!("/home/pi");
+/// # }
 /// ```
 #[macro_export]
 macro_rules! file_view {
-    ( $x:expr ) => { { FileView::new($x) } }
+    ( $x:expr ) => { { $crate::views::FileView::new($x) } }
+}
+
+/// Create a new view that runs a closure when opened.
+/// ```
+/// # #[macro_use] extern crate piscreen;
+/// # fn main() {
+/// menu_view![
+///     ("Say hello", func_view!(|| println!("Hello, world!")))
+/// ];
+/// # }
+/// ```
+#[macro_export]
+macro_rules! func_view {
+    ( $x:expr ) => { { $crate::views::FuncView::new($x) } }
 }
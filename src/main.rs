@@ -0,0 +1,168 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use ssd1306::displayrotation::DisplayRotation;
+
+use piscreen::{connect_display, ButtonPins, ButtonSet, Runtime};
+use piscreen::views::{validate, ConfigProblem, DynamicView};
+
+const USAGE: &str = "usage: piscreen [options] <menu.json>
+       piscreen --check <menu.json>
+
+Runs the menu described by <menu.json> on an SSD1306 display, reloading it
+whenever the file changes.
+
+options:
+  --check               check the menu file for problems and exit
+  --bus <n>             I2C bus of the display (default 1)
+  --address <addr>      I2C address of the display (default 0x3c)
+  --rotation <degrees>  display rotation: 0 or 180 (default 0)
+  --pins <pins>         button pins, e.g. a=5,b=6,c=4,up=17,down=22,left=27,right=23
+  --state <file>        save the open menu entries to <file> and reopen them on startup";
+
+/// Options given on the command line.
+struct Options {
+    file: String,
+    check: bool,
+    bus: u8,
+    address: u8,
+    rotation: DisplayRotation,
+    pins: ButtonPins,
+    state: Option<String>
+}
+
+/// Parse a number, which may be given in hex with a leading `0x`.
+fn parse_u8(value: &str) -> Result<u8, String> {
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => value.parse()
+    };
+    parsed.map_err(|_| format!("invalid number \"{}\"", value))
+}
+
+/// Parse a display rotation in degrees. Only 0 and 180 are supported, as the
+/// views are laid out for a 128x64 screen.
+fn parse_rotation(value: &str) -> Result<DisplayRotation, String> {
+    match value {
+        "0" => Ok(DisplayRotation::Rotate0),
+        "180" => Ok(DisplayRotation::Rotate180),
+        "90" | "270" => Err(format!("rotation {} is not supported, the views need a landscape screen", value)),
+        _ => Err(format!("invalid rotation \"{}\"", value))
+    }
+}
+
+/// Parse a list of `button=pin` pairs, starting from the default pins.
+fn parse_pins(value: &str) -> Result<ButtonPins, String> {
+    let mut pins = ButtonPins::default();
+    for pair in value.split(',') {
+        let mut parts = pair.splitn(2, '=');
+        let (button, pin) = match (parts.next(), parts.next()) {
+            (Some(button), Some(pin)) => (button.trim(), parse_u8(pin.trim())?),
+            _ => return Err(format!("invalid pin \"{}\"", pair))
+        };
+        match button {
+            "a" => pins.a = pin,
+            "b" => pins.b = pin,
+            "c" => pins.c = pin,
+            "up" => pins.up = pin,
+            "down" => pins.down = pin,
+            "left" => pins.left = pin,
+            "right" => pins.right = pin,
+            _ => return Err(format!("unknown button \"{}\"", button))
+        }
+    }
+    Ok(pins)
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut file = None;
+    let mut options = Options {
+        file: String::new(),
+        check: false,
+        bus: 1,
+        address: 0x3c,
+        rotation: DisplayRotation::Rotate0,
+        pins: ButtonPins::default(),
+        state: None
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--check" => options.check = true,
+            "--bus" => options.bus = parse_u8(value()?)?,
+            "--address" => options.address = parse_u8(value()?)?,
+            "--rotation" => options.rotation = parse_rotation(value()?)?,
+            "--pins" => options.pins = parse_pins(value()?)?,
+            "--state" => options.state = Some(value()?.to_owned()),
+            "-h" | "--help" => return Err(String::new()),
+            flag if flag.starts_with("--") => return Err(format!("unknown option {}", flag)),
+            _ if file.is_some() => return Err("only one menu file can be given".to_owned()),
+            path => file = Some(path.to_owned())
+        }
+    }
+    options.file = file.ok_or_else(|| "no menu file given".to_owned())?;
+    Ok(options)
+}
+
+/// Check a menu file, printing every problem found in it.
+/// Returns whether the file is valid, which it still is with only warnings.
+fn check(file: &str) -> bool {
+    let problems = match fs::read_to_string(file) {
+        Ok(json) => match serde_json::from_str(&json) {
+            Ok(config) => validate(&config),
+            Err(e) => vec![ConfigProblem { pointer: String::new(), message: format!("invalid JSON: {}", e), warning: false }]
+        },
+        Err(e) => vec![ConfigProblem { pointer: String::new(), message: format!("could not read file: {}", e), warning: false }]
+    };
+    for problem in &problems {
+        eprintln!("{}: {}", file, problem);
+    }
+    let valid = problems.iter().all(|problem| problem.warning);
+    if valid {
+        println!("{}: ok", file);
+    }
+    valid
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            if !e.is_empty() {
+                eprintln!("piscreen: {}\n", e);
+            }
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if options.check {
+        if !check(&options.file) {
+            process::exit(1);
+        }
+        return
+    }
+
+    let disp = match connect_display(options.bus, options.address, options.rotation) {
+        Ok(disp) => disp,
+        Err(e) => {
+            eprintln!("piscreen: could not connect to display: {}", e);
+            process::exit(1);
+        }
+    };
+    let mut root = match DynamicView::watch(&options.file) {
+        Ok(root) => root,
+        Err(e) => {
+            eprintln!("piscreen: could not watch {}: {}", options.file, e);
+            process::exit(1);
+        }
+    };
+    let mut runtime = Runtime::new(disp, ButtonSet::with_pins(&options.pins));
+    if let Some(state) = options.state {
+        runtime = runtime.with_state_file(state);
+    }
+    runtime.run(&mut root);
+}
@@ -0,0 +1,110 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::View;
+
+/// The names of the entries currently open beneath `root`, joined with slashes.
+pub fn current_path(root: &dyn View) -> String {
+    root.path().join("/")
+}
+
+/// Save the names of the entries currently open beneath `root` to a file, so
+/// that the same screen can be reopened with `restore_path` after a restart.
+pub fn save_path<P: AsRef<Path>>(root: &dyn View, file: P) -> io::Result<()> {
+    fs::write(file, serde_json::to_string(&root.path())?)
+}
+
+/// Reopen the entries saved by `save_path` beneath `root`, as far as they
+/// still exist. Returns the number of levels that were opened.
+/// ```
+/// # #[macro_use] extern crate piscreen;
+/// # use piscreen::*;
+/// # fn main() {
+/// let file = std::env::temp_dir().join("piscreen-path.json");
+/// let settings = || menu_view![
+///     ("Network", menu_view![("Wi-Fi", text_view!("Connected"))])
+/// ];
+///
+/// let mut menu = settings();
+/// menu.open_path(&["Network".to_owned(), "Wi-Fi".to_owned()]);
+/// save_path(&menu, &file).unwrap();
+///
+/// let mut menu = settings();
+/// assert_eq!(restore_path(&mut menu, &file).unwrap(), 2);
+/// assert_eq!(menu.path(), vec!["Network", "Wi-Fi"]);
+/// # }
+/// ```
+pub fn restore_path<P: AsRef<Path>>(root: &mut dyn View, file: P) -> io::Result<usize> {
+    let path: Vec<String> = serde_json::from_str(&fs::read_to_string(file)?)?;
+    Ok(root.open_path(&path))
+}
+
+/// Error returned by `navigate` when an entry in the path could not be found,
+/// or could not be opened past without confirmation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavigateError {
+    /// The name of the entry that could not be found or opened.
+    pub segment: String,
+    /// The number of levels that were opened before it.
+    pub depth: usize,
+    /// Whether the entry exists, but is waiting for its confirmation to be
+    /// accepted before the rest of the path can be opened.
+    pub needs_confirmation: bool
+}
+
+impl fmt::Display for NavigateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.needs_confirmation {
+            true => write!(f, "entry \"{}\" at level {} needs confirmation", self.segment, self.depth + 1),
+            false => write!(f, "no entry named \"{}\" at level {}", self.segment, self.depth + 1)
+        }
+    }
+}
+
+impl Error for NavigateError {}
+
+/// Open a slash-separated path of entry names beneath `root`, such as
+/// `"Settings/Network/Wi-Fi"`. If an entry is missing, the levels before it
+/// are left open and an error naming the missing entry is returned.
+///
+/// An entry with a confirmation shows it instead of opening, so a path can
+/// end there, but an error is returned if it goes on past it.
+/// ```
+/// # #[macro_use] extern crate piscreen;
+/// # use piscreen::*;
+/// # fn main() {
+/// let mut menu = menu_view![
+///     ("Settings", menu_view![
+///         ("Network", menu_view![("Wi-Fi", text_view!("Connected"))])
+///     ])
+/// ];
+/// assert!(navigate(&mut menu, "Settings/Network/Wi-Fi").is_ok());
+///
+/// let error = navigate(&mut menu, "Settings/Display").unwrap_err();
+/// assert_eq!(error.segment, "Display");
+/// assert_eq!(current_path(&menu), "Settings");
+///
+/// menu.add_entry(views::MenuEntry::new("Reset", Box::new(menu_view![("All", text_view!("Done"))]))
+///     .with_confirmation("Reset everything?"));
+/// let error = navigate(&mut menu, "Reset/All").unwrap_err();
+/// assert!(error.needs_confirmation);
+/// # }
+/// ```
+pub fn navigate(root: &mut dyn View, path: &str) -> Result<(), NavigateError> {
+    let segments: Vec<String> = path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_owned)
+        .collect();
+    let depth = root.open_path(&segments);
+    // An entry showing its confirmation counts as opened, but is left out of
+    // the open path until it has been confirmed.
+    let needs_confirmation = root.path().len() < depth;
+    match (segments.get(depth), needs_confirmation) {
+        (Some(_), true) => Err(NavigateError { segment: segments[depth - 1].clone(), depth: depth - 1, needs_confirmation }),
+        (Some(segment), false) => Err(NavigateError { segment: segment.clone(), depth, needs_confirmation }),
+        (None, _) => Ok(())
+    }
+}
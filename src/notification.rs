@@ -0,0 +1,89 @@
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use crate::show_toast;
+
+/// How important a notification is. Notifications are listed with the most
+/// important first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    Low,
+    Normal,
+    /// Also shown as a toast when raised.
+    High
+}
+
+/// An event kept in the notification center until it is dismissed.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub id: u64,
+    pub priority: Priority,
+    pub title: String,
+    pub text: String,
+    pub time: SystemTime,
+    pub read: bool
+}
+
+struct Notifications {
+    next_id: u64,
+    list: Vec<Notification>
+}
+
+static NOTIFICATIONS: Mutex<Notifications> = Mutex::new(Notifications { next_id: 0, list: Vec::new() });
+
+/// Add a notification to the notification center, returning its id.
+///
+/// This can be called from any view or thread. The number of unread
+/// notifications is shown in the footer of every menu, and they can be read
+/// in a `NotificationView`.
+/// ```
+/// use piscreen::{notify, Priority};
+/// notify(Priority::High, "Disk almost full", "Only 120MB left on /");
+/// ```
+pub fn notify(priority: Priority, title: &str, text: &str) -> u64 {
+    let mut notifications = NOTIFICATIONS.lock().unwrap();
+    let id = notifications.next_id;
+    notifications.next_id += 1;
+    notifications.list.push(Notification {
+        id,
+        priority,
+        title: title.to_owned(),
+        text: text.to_owned(),
+        time: SystemTime::now(),
+        read: false
+    });
+    if priority == Priority::High {
+        show_toast(title);
+    }
+    id
+}
+
+/// All notifications that have not been dismissed, most important first and
+/// then newest first.
+pub fn notifications() -> Vec<Notification> {
+    let mut list = NOTIFICATIONS.lock().unwrap().list.clone();
+    list.sort_by(|a, b| b.priority.cmp(&a.priority).then(b.id.cmp(&a.id)));
+    list
+}
+
+/// The number of notifications that have not been read.
+pub fn unread_notifications() -> usize {
+    NOTIFICATIONS.lock().unwrap().list.iter().filter(|n| !n.read).count()
+}
+
+/// Mark the notification with `id` as read.
+pub fn mark_read(id: u64) {
+    for notification in NOTIFICATIONS.lock().unwrap().list.iter_mut().filter(|n| n.id == id) {
+        notification.read = true;
+    }
+}
+
+/// Remove the notification with `id`.
+pub fn dismiss(id: u64) {
+    NOTIFICATIONS.lock().unwrap().list.retain(|n| n.id != id);
+}
+
+/// Remove every notification.
+pub fn dismiss_all() {
+    NOTIFICATIONS.lock().unwrap().list.clear();
+}
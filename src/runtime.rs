@@ -0,0 +1,90 @@
+use std::io;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use rppal::i2c::{self, I2c};
+use ssd1306::{Builder, displayrotation::DisplayRotation, mode::GraphicsMode};
+
+use crate::{ButtonSet, Display, View, render_toast, restore_path, save_path};
+
+/// Delay between frames, in milliseconds.
+const FRAME_DURATION: u64 = 20;
+
+/// Connect to and initialise an SSD1306 display on an I2C bus.
+///
+/// The views are laid out for a 128x64 screen, so `rotation` should be
+/// `Rotate0` or `Rotate180`.
+pub fn connect_display(bus: u8, address: u8, rotation: DisplayRotation) -> i2c::Result<Display> {
+    let mut disp: GraphicsMode<_> = Builder::new()
+        .with_i2c_addr(address)
+        .with_rotation(rotation)
+        .connect_i2c(I2c::with_bus(bus)?)
+        .into();
+    disp.init().map_err(|e| match e {
+        ssd1306::Error::Comm(e) => e,
+        ssd1306::Error::Pin(_) => i2c::Error::Io(io::Error::other("display pin error"))
+    })?;
+    Ok(disp)
+}
+
+/// Drives a root view: polls the buttons, passes them to the view and draws
+/// it to the display with any toast on top, forever.
+pub struct Runtime {
+    disp: Display,
+    buttons: ButtonSet,
+    state_file: Option<PathBuf>
+}
+
+impl Runtime {
+    /// Create a runtime drawing to `disp` and reading input from `buttons`.
+    pub fn new(disp: Display, buttons: ButtonSet) -> Runtime {
+        Runtime {
+            disp,
+            buttons,
+            state_file: None
+        }
+    }
+
+    /// Reopen the entries saved in `file` on startup, and save the open
+    /// entries to it whenever they change.
+    pub fn with_state_file<P: Into<PathBuf>>(mut self, file: P) -> Runtime {
+        self.state_file = Some(file.into());
+        self
+    }
+
+    /// Run `root` as the top level view. Never returns.
+    pub fn run(&mut self, root: &mut dyn View) -> ! {
+        root.activate();
+        if let Some(file) = &self.state_file {
+            if let Err(e) = restore_path(root, file) {
+                if e.kind() != io::ErrorKind::NotFound {
+                    eprintln!("could not restore {}: {}", file.display(), e);
+                }
+            }
+        }
+        let mut last_path = root.path();
+        loop {
+            self.buttons.poll_all();
+            root.handle_buttons(&mut self.buttons);
+
+            self.disp.clear();
+            root.render(&mut self.disp);
+            render_toast(&mut self.disp);
+            if let Err(e) = self.disp.flush() {
+                eprintln!("could not draw to display: {:?}", e);
+            }
+
+            if let Some(file) = &self.state_file {
+                let path = root.path();
+                if path != last_path {
+                    if let Err(e) = save_path(root, file) {
+                        eprintln!("could not save {}: {}", file.display(), e);
+                    }
+                    last_path = path;
+                }
+            }
+            thread::sleep(Duration::from_millis(FRAME_DURATION));
+        }
+    }
+}
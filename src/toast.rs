@@ -0,0 +1,80 @@
+use embedded_graphics::fonts::{Font,Font6x8};
+use embedded_graphics::coord::Coord;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rect;
+use embedded_graphics::Drawing;
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::{Display, ON, OFF, views::wrap};
+
+/// How long a toast is shown for by default, in milliseconds.
+const TOAST_DURATION: u64 = 2000;
+/// Number of lines of a toast that are shown.
+const TOAST_LINES: usize = 2;
+
+struct Toast {
+    message: String,
+    duration: Duration,
+    shown: Option<SystemTime>
+}
+
+/// Toasts waiting to be shown, oldest first. The first one is on screen.
+static TOASTS: Mutex<VecDeque<Toast>> = Mutex::new(VecDeque::new());
+
+/// Show a short message in a box on top of the screen for two seconds.
+///
+/// This can be called from any view or thread. Toasts are shown one after
+/// another, in the order they were raised.
+/// ```
+/// piscreen::show_toast("Backup finished");
+/// ```
+pub fn show_toast(message: &str) {
+    show_toast_for(message, Duration::from_millis(TOAST_DURATION));
+}
+
+/// Show a toast like `show_toast`, for `duration`.
+pub fn show_toast_for(message: &str, duration: Duration) {
+    TOASTS.lock().unwrap().push_back(Toast {
+        message: message.to_owned(),
+        duration,
+        shown: None
+    });
+}
+
+/// Draw the current toast, if there is one, over whatever has been drawn.
+///
+/// `Runtime` calls this after rendering the root view. Call it after
+/// rendering when driving views without a `Runtime`.
+pub fn render_toast(disp: &mut Display) {
+    let mut toasts = TOASTS.lock().unwrap();
+    let now = SystemTime::now();
+    while let Some(toast) = toasts.front() {
+        match toast.shown {
+            Some(shown) if now.duration_since(shown).unwrap_or_default() >= toast.duration => {
+                toasts.pop_front();
+            }
+            _ => break
+        }
+    }
+    let toast = match toasts.front_mut() {
+        Some(toast) => toast,
+        None => return
+    };
+    toast.shown.get_or_insert(now);
+    let lines = wrap(&toast.message);
+    let lines = &lines[..std::cmp::min(lines.len(), TOAST_LINES)];
+    let height = 9 * lines.len() as i32 + 7;
+    let top = (64 - height) / 2;
+    disp.draw(Rect::new(Coord::new(0, top), Coord::new(127, top + height))
+        .with_stroke(ON)
+        .with_fill(OFF)
+        .into_iter());
+    for (i, line) in lines.iter().enumerate() {
+        disp.draw(Font6x8::render_str(line)
+            .translate(Coord::new(3, top + 4 + 9 * i as i32))
+            .into_iter());
+    }
+}
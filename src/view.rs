@@ -26,4 +26,14 @@ pub trait View {
 
     /// Activate the view before being rendered for the first time
     fn activate(&mut self) {}
+
+    /// Tell the view the names of the entries opened to reach it, outermost first.
+    fn set_ancestors(&mut self, _ancestors: &[String]) {}
+
+    /// Names of the entries opened beneath this view, outermost first.
+    fn path(&self) -> Vec<String> { vec![] }
+
+    /// Open the entries named by `path` beneath this view, one level at a time.
+    /// Returns the number of levels that were opened.
+    fn open_path(&mut self, _path: &[String]) -> usize { 0 }
 }
@@ -0,0 +1,141 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::{
+    View, ReturnState, ReturnStateEnum::*, Display,
+    buttons::ButtonSet,
+    views::{MenuView, MenuEntry}
+};
+
+/// Label of the entry that confirms the selection.
+const DONE: &str = "Done";
+
+/// Callback given the selected items when the selection is confirmed.
+type OnConfirm = Box<dyn FnMut(&[String])>;
+
+/// A list of items with a checkbox on each row, for choosing several at once.
+///
+/// A toggles the selected item, and tapping C selects every item, or none if
+/// they are all selected already. Holding C filters the list as in a
+/// `MenuView`. Choosing "Done" at the end of the list calls the
+/// confirm callback and returns the selected items to the parent view as
+/// `Text`, one per line. B leaves and puts back the selection the list was
+/// opened with.
+/// ```
+/// # use piscreen::views::ChecklistView;
+/// ChecklistView::new("Restart", &["nginx", "postgresql", "redis"])
+///     .with_selected(&[0])
+///     .with_on_confirm(|services| {
+///         for service in services {
+///             println!("Restarting {}", service);
+///         }
+///     });
+/// ```
+pub struct ChecklistView {
+    items: Vec<String>,
+    states: Vec<Rc<Cell<bool>>>,
+    original: Vec<bool>,
+    confirmed: Rc<Cell<bool>>,
+    on_confirm: Option<OnConfirm>,
+    menu: MenuView
+}
+
+impl ChecklistView {
+    /// Create a list of `items`, none of them selected.
+    pub fn new(name: &str, items: &[&str]) -> ChecklistView {
+        let states: Vec<Rc<Cell<bool>>> = items.iter().map(|_| Rc::new(Cell::new(false))).collect();
+        let confirmed = Rc::new(Cell::new(false));
+        let mut menu = MenuView::new();
+        menu.set_name(name);
+        for (item, state) in items.iter().zip(&states) {
+            menu.add_entry(MenuEntry::toggle(item, state.clone()));
+        }
+        let done = confirmed.clone();
+        menu.add_entry(MenuEntry::action(DONE, move || done.set(true)));
+        ChecklistView {
+            items: items.iter().map(|item| (*item).to_owned()).collect(),
+            states,
+            original: vec![false; items.len()],
+            confirmed,
+            on_confirm: None,
+            menu
+        }
+    }
+
+    /// Start with the items at `indices` selected.
+    pub fn with_selected(mut self, indices: &[usize]) -> ChecklistView {
+        for &i in indices {
+            if let Some(state) = self.states.get(i) {
+                state.set(true);
+            }
+        }
+        self.original = self.states.iter().map(|state| state.get()).collect();
+        self
+    }
+
+    /// Call `on_confirm` with the selected items whenever the selection is confirmed.
+    pub fn with_on_confirm<F: FnMut(&[String]) + 'static>(mut self, on_confirm: F) -> ChecklistView {
+        self.on_confirm = Some(Box::new(on_confirm));
+        self
+    }
+
+    /// The indices of the selected items.
+    pub fn selected(&self) -> Vec<usize> {
+        self.states.iter().enumerate()
+            .filter(|(_, state)| state.get())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// The selected items.
+    pub fn selected_items(&self) -> Vec<String> {
+        self.selected().into_iter().map(|i| self.items[i].clone()).collect()
+    }
+
+    /// Select every item, or none if they are all selected.
+    fn toggle_all(&mut self) {
+        let all = self.states.iter().all(|state| state.get());
+        for state in &self.states {
+            state.set(!all);
+        }
+    }
+}
+
+impl View for ChecklistView {
+    fn render(&mut self, disp: &mut Display) {
+        self.menu.render(disp);
+    }
+
+    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        if buttons.c.was_tapped() {
+            self.toggle_all();
+        }
+        match self.menu.handle_buttons(buttons) {
+            Some(Pop) => {
+                for (state, &original) in self.states.iter().zip(&self.original) {
+                    state.set(original);
+                }
+                Some(Pop)
+            }
+            _ if self.confirmed.get() => {
+                self.confirmed.set(false);
+                let selected = self.selected_items();
+                if let Some(on_confirm) = &mut self.on_confirm {
+                    on_confirm(&selected);
+                }
+                Some(Text(selected.join("\n")))
+            }
+            state => state
+        }
+    }
+
+    fn activate(&mut self) {
+        self.confirmed.set(false);
+        self.original = self.states.iter().map(|state| state.get()).collect();
+        self.menu.first_entry();
+    }
+
+    fn set_ancestors(&mut self, ancestors: &[String]) {
+        self.menu.set_ancestors(ancestors);
+    }
+}
@@ -0,0 +1,249 @@
+use embedded_graphics::fonts::{Font,Font6x8};
+use embedded_graphics::coord::Coord;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rect;
+use embedded_graphics::Drawing;
+
+use std::io::{ErrorKind, Read};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{
+    buttons::ButtonSet,
+    views::{progress::spinner, TextView, ON},
+    View, ReturnState, ReturnStateEnum::*, Display
+};
+
+/// How long to wait for the output pipes to close after the program exits,
+/// in milliseconds.
+const DRAIN_DURATION: u64 = 200;
+
+/// What has been read from a pipe so far.
+#[derive(Default)]
+struct PipeOutput {
+    bytes: Vec<u8>,
+    /// Whether the pipe has been read to its end.
+    closed: bool
+}
+
+/// Output read from a pipe, shared with the thread reading it.
+type Output = Arc<Mutex<PipeOutput>>;
+
+enum CommandState {
+    Idle,
+    Running {
+        child: Child,
+        started: Instant,
+        stdout: Output,
+        stderr: Output
+    },
+    Finished(TextView)
+}
+
+/// A view that runs a program when activated, shows a spinner while it runs
+/// and then shows its exit status and output.
+///
+/// The program runs in its own process group, and pressing B while it is
+/// running kills the whole group.
+/// ```
+/// # use piscreen::views::CommandView;
+/// # use std::time::Duration;
+/// CommandView::new("df", &["-h"])
+///     .with_timeout(Duration::from_secs(10));
+/// ```
+pub struct CommandView {
+    program: String,
+    args: Vec<String>,
+    timeout: Option<Duration>,
+    state: CommandState
+}
+
+/// Read everything from a pipe on another thread, so that the program is
+/// never blocked writing to a full pipe.
+///
+/// The output is shared as it is read rather than returned at the end, as a
+/// background process started by the program can keep the pipe open long
+/// after the program itself has exited.
+fn read_all<R: Read + Send + 'static>(pipe: Option<R>) -> Output {
+    let output = Output::default();
+    let shared = output.clone();
+    thread::spawn(move || {
+        if let Some(mut pipe) = pipe {
+            let mut buffer = [0; 1024];
+            loop {
+                match pipe.read(&mut buffer) {
+                    Ok(0) => break,
+                    Ok(n) => shared.lock().unwrap().bytes.extend_from_slice(&buffer[..n]),
+                    Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                    Err(_) => break
+                }
+            }
+        }
+        shared.lock().unwrap().closed = true;
+    });
+    output
+}
+
+impl CommandView {
+    /// Create a view that runs `program` with `args`.
+    pub fn new(program: &str, args: &[&str]) -> CommandView {
+        CommandView {
+            program: program.to_owned(),
+            args: args.iter().map(|arg| (*arg).to_owned()).collect(),
+            timeout: None,
+            state: CommandState::Idle
+        }
+    }
+
+    /// Kill the program if it is still running after `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> CommandView {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Whether the program is currently running.
+    pub fn is_running(&self) -> bool {
+        matches!(self.state, CommandState::Running { .. })
+    }
+
+    /// Kill the program and any processes it started if it is running, and
+    /// wait for it to exit.
+    fn kill(&mut self) -> Option<(ExitStatus, String)> {
+        match std::mem::replace(&mut self.state, CommandState::Idle) {
+            CommandState::Running { mut child, stdout, stderr, .. } => {
+                // The program leads its own process group, so this reaches its children too.
+                unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL); }
+                let status = child.wait().ok()?;
+                Some((status, CommandView::collect(&stdout, &stderr)))
+            }
+            state => {
+                self.state = state;
+                None
+            }
+        }
+    }
+
+    /// Combine the output of a program that has exited. Waits a short while
+    /// for the pipes to be read to their end, but gives up and uses what has
+    /// been read if a process started by the program still holds them open.
+    fn collect(stdout: &Output, stderr: &Output) -> String {
+        let draining = Instant::now();
+        while !(stdout.lock().unwrap().closed && stderr.lock().unwrap().closed)
+            && draining.elapsed() < Duration::from_millis(DRAIN_DURATION) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        let stdout = stdout.lock().unwrap();
+        let stderr = stderr.lock().unwrap();
+        let mut output = String::from_utf8_lossy(&stdout.bytes).trim_end().to_owned();
+        let stderr = String::from_utf8_lossy(&stderr.bytes);
+        if !stderr.trim().is_empty() {
+            output.push('\n');
+            output.push_str(stderr.trim_end());
+        }
+        output
+    }
+
+    /// Show the result of the program.
+    fn finish(&mut self, summary: &str, output: &str) {
+        let text = format!("{}\n{}", summary, output);
+        self.state = CommandState::Finished(TextView::new(&text));
+    }
+
+    /// Check whether the program has exited or timed out.
+    fn poll(&mut self) {
+        let (exited, timed_out) = match &mut self.state {
+            CommandState::Running { child, started, .. } => (
+                child.try_wait().ok().flatten(),
+                match self.timeout {
+                    Some(timeout) => started.elapsed() >= timeout,
+                    None => false
+                }
+            ),
+            _ => return
+        };
+        if let Some(status) = exited {
+            if let CommandState::Running { stdout, stderr, .. } = std::mem::replace(&mut self.state, CommandState::Idle) {
+                let output = CommandView::collect(&stdout, &stderr);
+                let summary = match status.code() {
+                    Some(code) => format!("Exit status {}", code),
+                    None => "Killed by a signal".to_owned()
+                };
+                self.finish(&summary, &output);
+            }
+        } else if timed_out {
+            let output = self.kill().map(|(_, output)| output).unwrap_or_default();
+            let summary = format!("Timed out after {}s", self.timeout.unwrap().as_secs_f64());
+            self.finish(&summary, &output);
+        }
+    }
+}
+
+impl View for CommandView {
+    fn render(&mut self, disp: &mut Display) {
+        match &mut self.state {
+            CommandState::Running { started, .. } => {
+                disp.draw(Font6x8::render_str(&format!("Running {}", spinner(started.elapsed())))
+                    .translate(Coord::new(3, 3))
+                    .into_iter());
+                for (i, line) in TextView::new(&self.program).get_lines().iter().take(5).enumerate() {
+                    disp.draw(Font6x8::render_str(line)
+                        .translate(Coord::new(3, 3 + 9 * (i as i32 + 1)))
+                        .into_iter());
+                }
+                disp.draw(Rect::new(Coord::new(0, 0), Coord::new(127, 63))
+                    .with_stroke(ON)
+                    .into_iter());
+            }
+            CommandState::Finished(text) => text.render(disp),
+            CommandState::Idle => {}
+        }
+    }
+
+    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        self.poll();
+        match &mut self.state {
+            CommandState::Finished(text) => text.handle_buttons(buttons),
+            CommandState::Running { .. } => {
+                if buttons.b.was_pressed() {
+                    self.kill();
+                    return Some(Pop)
+                }
+                None
+            }
+            CommandState::Idle => Some(Pop)
+        }
+    }
+
+    fn activate(&mut self) {
+        self.kill();
+        let spawned = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .process_group(0)
+            .spawn();
+        match spawned {
+            Ok(mut child) => {
+                let stdout = read_all(child.stdout.take());
+                let stderr = read_all(child.stderr.take());
+                self.state = CommandState::Running {
+                    child,
+                    started: Instant::now(),
+                    stdout,
+                    stderr
+                };
+            }
+            Err(e) => self.finish(&format!("Could not run {}", self.program), &e.to_string())
+        }
+    }
+}
+
+impl Drop for CommandView {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
@@ -0,0 +1,120 @@
+use embedded_graphics::fonts::{Font,Font6x8};
+use embedded_graphics::coord::Coord;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rect;
+use embedded_graphics::Drawing;
+
+use crate::{
+    buttons::ButtonSet,
+    views::{text::wrap, ON, OFF},
+    View, ReturnState, ReturnStateEnum::*, Display
+};
+
+/// Number of message lines that fit above the buttons.
+const MESSAGE_LINES: usize = 5;
+
+/// A dialog that shows a message and a row of buttons.
+///
+/// Left and right choose a button, and A returns its label to the parent view
+/// as `Text`. B returns `Pop` without choosing. The first button confirms, so
+/// a plain dialog asks "Yes" or "No", and starts on "No".
+/// ```
+/// # use piscreen::views::ConfirmView;
+/// ConfirmView::new("Reboot now?");
+/// ConfirmView::new("Save changes to notes.txt?")
+///     .with_choices(&["Save", "Discard", "Cancel"], 2);
+/// ```
+pub struct ConfirmView {
+    message: String,
+    choices: Vec<String>,
+    default: usize,
+    selected: usize,
+    chosen: Option<usize>
+}
+
+impl ConfirmView {
+    /// Create a Yes/No dialog showing `message`.
+    pub fn new(message: &str) -> ConfirmView {
+        ConfirmView {
+            message: message.to_owned(),
+            choices: vec!["Yes".to_owned(), "No".to_owned()],
+            default: 1,
+            selected: 1,
+            chosen: None
+        }
+    }
+
+    /// Replace the buttons with `choices`, starting on the one at `default`.
+    pub fn with_choices(mut self, choices: &[&str], default: usize) -> ConfirmView {
+        self.choices = choices.iter().map(|choice| (*choice).to_owned()).collect();
+        self.default = std::cmp::min(default, self.choices.len().saturating_sub(1));
+        self.selected = self.default;
+        self
+    }
+
+    /// The index of the button chosen with A since the dialog was last
+    /// activated, if one was.
+    pub fn chosen(&self) -> Option<usize> {
+        self.chosen
+    }
+
+    /// Whether the first button was chosen.
+    pub fn is_confirmed(&self) -> bool {
+        self.chosen == Some(0)
+    }
+}
+
+impl View for ConfirmView {
+    fn render(&mut self, disp: &mut Display) {
+        for (i, line) in wrap(&self.message).iter().take(MESSAGE_LINES).enumerate() {
+            disp.draw(Font6x8::render_str(line)
+                .translate(Coord::new(3, 3 + 9 * i as i32))
+                .into_iter());
+        }
+        if self.choices.is_empty() {
+            return
+        }
+        let width = 128 / self.choices.len() as i32;
+        for (i, choice) in self.choices.iter().enumerate() {
+            let is_selected = i == self.selected;
+            let left = i as i32 * width;
+            disp.draw(Rect::new(Coord::new(left, 50), Coord::new(left + width - 1, 63))
+                .with_stroke(ON)
+                .with_fill(match is_selected { true => ON, false => OFF })
+                .into_iter());
+            let label: String = choice.chars().take((width as usize - 2) / 6).collect();
+            let x = left + (width - 6 * label.chars().count() as i32) / 2;
+            disp.draw(Font6x8::render_str(&label)
+                .with_stroke(match !is_selected { true => ON, false => OFF })
+                .with_fill(match is_selected { true => ON, false => OFF })
+                .translate(Coord::new(x, 53))
+                .into_iter());
+        }
+    }
+
+    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        let len = self.choices.len();
+        if len == 0 {
+            return Some(Pop)
+        }
+        if buttons.left.was_pressed() {
+            self.selected = (self.selected + len - 1) % len;
+        }
+        if buttons.right.was_pressed() {
+            self.selected = (self.selected + 1) % len;
+        }
+        if buttons.a.was_pressed() {
+            self.chosen = Some(self.selected);
+            return Some(Text(self.choices[self.selected].clone()))
+        }
+        if buttons.b.was_pressed() {
+            return Some(Pop)
+        }
+        None
+    }
+
+    fn activate(&mut self) {
+        self.selected = self.default;
+        self.chosen = None;
+    }
+}
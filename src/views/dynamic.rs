@@ -1,44 +1,439 @@
-extern crate embedded_graphics;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-// use embedded_graphics::fonts::{Font,Font6x8};
-// use embedded_graphics::coord::Coord;
-// use embedded_graphics::prelude::*;
-// use embedded_graphics::image::Image1BPP;
-// use embedded_graphics::primitives::Rect;
-// use embedded_graphics::primitives::Line;
-// use embedded_graphics::Drawing;
+use serde_json::Value;
 
 use crate::{
     buttons::ButtonSet,
-    views::{ON, OFF},
-    View, ReturnState, ReturnStateEnum::*, Display
+    views::{MenuView, MenuEntry, TextView, TextInputView, FileView, ScriptView, CommandView, StreamView, NotificationView},
+    watcher::FileWatcher,
+    View, ReturnState, Display, show_toast
 };
 
-pub struct DynamicView {
-    
+/// A problem found in a view tree config, and where it was found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigProblem {
+    /// JSON pointer to the value with the problem, e.g. `/root/entries/2`.
+    pub pointer: String,
+    /// Description of the problem.
+    pub message: String,
+    /// Whether the problem is only a warning, such as a path that does not
+    /// exist yet, which does not stop the config from being loaded.
+    pub warning: bool
 }
 
-impl View for DynamicView {
-    fn render(&mut self, disp: &mut Display) {
+impl ConfigProblem {
+    fn new(pointer: &str, message: String) -> ConfigProblem {
+        ConfigProblem { pointer: pointer.to_owned(), message, warning: false }
+    }
 
+    fn warning(pointer: &str, message: String) -> ConfigProblem {
+        ConfigProblem { warning: true, ..ConfigProblem::new(pointer, message) }
     }
-    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState { None }
 }
 
-/*
-{
-    root: {
-        type: "menu",
-        entries: [
-            {
-                type: "file",
-                dir: "~"
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.warning {
+            write!(f, "warning: ")?;
+        }
+        match self.pointer.is_empty() {
+            true => write!(f, "{}", self.message),
+            false => write!(f, "{}: {}", self.pointer, self.message)
+        }
+    }
+}
+
+/// Error returned when a view tree could not be loaded.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The config file could not be read.
+    Io(io::Error),
+    /// The config is not valid JSON.
+    Json(serde_json::Error),
+    /// The config is valid JSON, but does not describe a valid view tree.
+    Invalid(Vec<ConfigProblem>)
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "could not read config: {}", e),
+            ConfigError::Json(e) => write!(f, "invalid JSON: {}", e),
+            ConfigError::Invalid(problems) => {
+                let problems: Vec<String> = problems.iter().map(ConfigProblem::to_string).collect();
+                write!(f, "{}", problems.join("\n"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<io::Error> for ConfigError {
+    fn from(e: io::Error) -> ConfigError { ConfigError::Io(e) }
+}
+
+impl From<serde_json::Error> for ConfigError {
+    fn from(e: serde_json::Error) -> ConfigError { ConfigError::Json(e) }
+}
+
+/// A view tree loaded from a JSON document, so that menus can be changed
+/// without recompiling.
+///
+/// The document has a `root` node. Every node has a `type`, and nodes inside
+/// a menu's `entries` also have a `name`. An entry with a `confirm` message
+/// asks it before opening:
+///
+/// - `menu`: a `MenuView` of `entries`, with an optional `name`.
+/// - `text`: a `TextView` showing `text`.
+/// - `file`: a `FileView` of the directory `dir`. A leading `~` is replaced
+///   with the home directory.
+/// - `scripts`: a `ScriptView` of the executable scripts in the directory
+///   `dir`, with `~` replaced like `file`.
+/// - `input`: a `TextInputView`.
+/// - `notifications`: a `NotificationView` of the notification center.
+/// - `command`: a `CommandView` that runs `command` with the optional list of
+///   `args` and shows its output. It is killed after `timeout` seconds, if
+///   given. Only allowed as a menu entry.
+/// - `stream`: a `StreamView` that runs `command` with `args` like `command`,
+///   but shows its output as it arrives. Only allowed as a menu entry.
+///
+/// ```
+/// # use piscreen::views::DynamicView;
+/// let view = DynamicView::from_json(r#"{
+///     "root": {
+///         "type": "menu",
+///         "entries": [
+///             { "name": "Home", "type": "file", "dir": "~" },
+///             { "name": "About", "type": "text", "text": "Hello, world!" },
+///             { "name": "Reboot", "type": "command", "command": "sudo", "args": ["reboot"],
+///               "confirm": "Reboot now?" }
+///         ]
+///     }
+/// }"#);
+/// assert!(view.is_ok());
+/// ```
+///
+/// Configs are checked with `validate` before they are loaded, so that every
+/// problem can be reported at once. Paths that do not exist are only warnings,
+/// and the view shows them as empty or failing when opened.
+pub struct DynamicView {
+    root: Box<dyn View>,
+    loaded: bool,
+    watched: Option<(PathBuf, FileWatcher)>
+}
+
+impl DynamicView {
+    fn with_root(root: Box<dyn View>) -> DynamicView {
+        DynamicView {
+            root,
+            loaded: true,
+            watched: None
+        }
+    }
+
+    /// Load a view tree from a JSON string.
+    pub fn from_json(json: &str) -> Result<DynamicView, ConfigError> {
+        let config: Value = serde_json::from_str(json)?;
+        let problems: Vec<ConfigProblem> = validate(&config).into_iter()
+            .filter(|problem| !problem.warning)
+            .collect();
+        if !problems.is_empty() {
+            return Err(ConfigError::Invalid(problems))
+        }
+        Ok(DynamicView::with_root(build_view(&config["root"])))
+    }
+
+    /// Load a view tree from a JSON file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<DynamicView, ConfigError> {
+        DynamicView::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Load a view tree from a JSON file, or show why it could not be loaded
+    /// on the screen instead.
+    pub fn from_file_or_error<P: AsRef<Path>>(path: P) -> DynamicView {
+        match DynamicView::from_file(&path) {
+            Ok(view) => view,
+            Err(e) => DynamicView::error_screen(&path, &e)
+        }
+    }
+
+    /// A view explaining why the config at `path` could not be loaded.
+    fn error_screen<P: AsRef<Path>>(path: P, error: &ConfigError) -> DynamicView {
+        let text = format!("Could not load {}\n\n{}", path.as_ref().display(), error);
+        let mut view = DynamicView::with_root(Box::new(TextView::new(&text)));
+        view.loaded = false;
+        view
+    }
+
+    /// Load a view tree from a JSON file like `from_file_or_error`, and reload
+    /// it whenever the file changes. The same entries are reopened after a
+    /// reload if they still exist, and a toast is shown saying whether the
+    /// reload worked.
+    pub fn watch<P: AsRef<Path>>(path: P) -> io::Result<DynamicView> {
+        let watcher = FileWatcher::new(path.as_ref())?;
+        let mut view = DynamicView::from_file_or_error(&path);
+        view.watched = Some((path.as_ref().to_owned(), watcher));
+        Ok(view)
+    }
+
+    /// Rebuild the view tree if the watched file has changed.
+    fn reload_if_changed(&mut self) {
+        let path = match &mut self.watched {
+            Some((path, watcher)) => match watcher.changed() {
+                true => path.clone(),
+                false => return
             },
-            {
-                type: "text",
-                text: "Hello, world!"
+            None => return
+        };
+        match DynamicView::from_file(&path) {
+            Ok(view) => {
+                let open = self.root.path();
+                self.root = view.root;
+                self.loaded = true;
+                self.root.activate();
+                self.root.open_path(&open);
+                show_toast("Menu reloaded");
+            }
+            Err(e) => {
+                if !self.loaded {
+                    self.root = DynamicView::error_screen(&path, &e).root;
+                }
+                let message = e.to_string();
+                show_toast(&format!("Menu not reloaded: {}", message.lines().next().unwrap_or_default()));
             }
-        ]
+        }
+    }
+}
+
+/// Check that a config describes a valid view tree, returning every problem
+/// that was found. Missing directories and commands are returned as warnings.
+/// ```
+/// # use piscreen::views::validate;
+/// let config = serde_json::json!({
+///     "root": { "type": "menu", "entries": [
+///         { "name": "About", "type": "txt" },
+///         { "name": "Logs", "type": "file", "dir": "/does/not/exist" }
+///     ] }
+/// });
+/// let problems = validate(&config);
+/// assert_eq!(problems[0].pointer, "/root/entries/0/type");
+/// assert!(!problems[0].warning);
+/// assert_eq!(problems[1].pointer, "/root/entries/1/dir");
+/// assert!(problems[1].warning);
+/// ```
+pub fn validate(config: &Value) -> Vec<ConfigProblem> {
+    let mut problems = vec![];
+    match config.get("root") {
+        Some(root) => validate_node(root, "/root", false, &mut problems),
+        None => problems.push(ConfigProblem::new("", "missing field \"root\"".to_owned()))
+    }
+    problems
+}
+
+/// Check that a string field is present on a node, returning it if it is.
+fn require_str<'a>(node: &'a Value, pointer: &str, field: &str, problems: &mut Vec<ConfigProblem>) -> Option<&'a str> {
+    match node.get(field) {
+        Some(Value::String(value)) => Some(value),
+        Some(_) => {
+            problems.push(ConfigProblem::new(&format!("{}/{}", pointer, field), "must be a string".to_owned()));
+            None
+        }
+        None => {
+            problems.push(ConfigProblem::new(pointer, format!("missing field \"{}\"", field)));
+            None
+        }
+    }
+}
+
+/// Check a single node, and the nodes beneath it.
+fn validate_node(node: &Value, pointer: &str, is_entry: bool, problems: &mut Vec<ConfigProblem>) {
+    if !node.is_object() {
+        problems.push(ConfigProblem::new(pointer, "node must be an object".to_owned()));
+        return
+    }
+    if is_entry {
+        require_str(node, pointer, "name", problems);
+        if node.get("confirm").is_some_and(|confirm| !confirm.is_string()) {
+            problems.push(ConfigProblem::new(&format!("{}/confirm", pointer), "must be a string".to_owned()));
+        }
+    }
+    let node_type = match require_str(node, pointer, "type", problems) {
+        Some(node_type) => node_type,
+        None => return
+    };
+    match node_type {
+        "menu" => {
+            if let Some(name) = node.get("name") {
+                if !name.is_string() {
+                    problems.push(ConfigProblem::new(&format!("{}/name", pointer), "must be a string".to_owned()));
+                }
+            }
+            let entries = match node.get("entries") {
+                Some(Value::Array(entries)) => entries,
+                Some(_) => {
+                    problems.push(ConfigProblem::new(&format!("{}/entries", pointer), "must be an array".to_owned()));
+                    return
+                }
+                None => {
+                    problems.push(ConfigProblem::new(pointer, "missing field \"entries\"".to_owned()));
+                    return
+                }
+            };
+            let mut names: Vec<&str> = vec![];
+            for (i, entry) in entries.iter().enumerate() {
+                let entry_pointer = format!("{}/entries/{}", pointer, i);
+                validate_node(entry, &entry_pointer, true, problems);
+                if let Some(name) = entry.get("name").and_then(Value::as_str) {
+                    if names.contains(&name) {
+                        problems.push(ConfigProblem::new(
+                            &format!("{}/name", entry_pointer),
+                            format!("duplicate entry name \"{}\"", name)
+                        ));
+                    }
+                    names.push(name);
+                }
+            }
+        }
+        "text" => { require_str(node, pointer, "text", problems); }
+        "file" | "scripts" => {
+            if let Some(dir) = require_str(node, pointer, "dir", problems) {
+                if !Path::new(&expand_home(dir)).is_dir() {
+                    problems.push(ConfigProblem::warning(
+                        &format!("{}/dir", pointer),
+                        format!("\"{}\" is not a directory", dir)
+                    ));
+                }
+            }
+        }
+        "input" | "notifications" => {}
+        "command" | "stream" => {
+            if !is_entry {
+                problems.push(ConfigProblem::new(pointer, format!("{} nodes can only be menu entries", node_type)));
+            }
+            if let Some(command) = require_str(node, pointer, "command", problems) {
+                if command.contains('/') && !Path::new(&expand_home(command)).is_file() {
+                    problems.push(ConfigProblem::warning(
+                        &format!("{}/command", pointer),
+                        format!("\"{}\" does not exist", command)
+                    ));
+                }
+            }
+            match node.get("args") {
+                Some(Value::Array(args)) => {
+                    for (i, arg) in args.iter().enumerate() {
+                        if !arg.is_string() {
+                            problems.push(ConfigProblem::new(&format!("{}/args/{}", pointer, i), "must be a string".to_owned()));
+                        }
+                    }
+                }
+                Some(_) => problems.push(ConfigProblem::new(&format!("{}/args", pointer), "must be an array".to_owned())),
+                None => {}
+            }
+            if let Some(timeout) = node.get("timeout") {
+                if !timeout.as_f64().is_some_and(|timeout| timeout > 0.0) {
+                    problems.push(ConfigProblem::new(&format!("{}/timeout", pointer), "must be a positive number".to_owned()));
+                }
+            }
+        }
+        other => problems.push(ConfigProblem::new(
+            &format!("{}/type", pointer),
+            format!("unknown node type \"{}\"", other)
+        ))
+    }
+}
+
+/// Get a string field from a node that has already been validated.
+fn get_str<'a>(node: &'a Value, field: &str) -> &'a str {
+    node.get(field).and_then(Value::as_str).unwrap_or_default()
+}
+
+/// Replace a leading `~` in a path with the home directory.
+fn expand_home(dir: &str) -> String {
+    match (dir.strip_prefix('~'), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}{}", home, rest),
+        _ => dir.to_owned()
+    }
+}
+
+/// Build the view described by a node that has already been validated.
+fn build_view(node: &Value) -> Box<dyn View> {
+    match get_str(node, "type") {
+        "menu" => {
+            let mut menu = MenuView::new();
+            if let Some(name) = node.get("name").and_then(Value::as_str) {
+                menu.set_name(name);
+            }
+            for entry in node["entries"].as_array().into_iter().flatten() {
+                menu.add_entry(build_entry(entry));
+            }
+            Box::new(menu)
+        }
+        "text" => Box::new(TextView::new(get_str(node, "text"))),
+        "file" => Box::new(FileView::new(&expand_home(get_str(node, "dir")))),
+        "scripts" => Box::new(ScriptView::new(&expand_home(get_str(node, "dir")))),
+        "notifications" => Box::new(NotificationView::new()),
+        "input" => Box::new(TextInputView::new()),
+        other => unreachable!("node type \"{}\" passed validation but has no view", other)
+    }
+}
+
+/// Build a menu entry from a node in a menu's `entries`.
+fn build_entry(node: &Value) -> MenuEntry {
+    let name = get_str(node, "name");
+    let entry = match get_str(node, "type") {
+        "command" => {
+            let args: Vec<&str> = node.get("args").and_then(Value::as_array)
+                .map(|args| args.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            let mut command = CommandView::new(get_str(node, "command"), &args);
+            let timeout = node.get("timeout").and_then(Value::as_f64);
+            if let Some(timeout) = timeout.and_then(|timeout| Duration::try_from_secs_f64(timeout).ok()) {
+                command = command.with_timeout(timeout);
+            }
+            MenuEntry::new(name, Box::new(command))
+        }
+        "stream" => {
+            let args: Vec<&str> = node.get("args").and_then(Value::as_array)
+                .map(|args| args.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            MenuEntry::new(name, Box::new(StreamView::new(get_str(node, "command"), &args)))
+        }
+        _ => MenuEntry::new(name, build_view(node))
+    };
+    match node.get("confirm").and_then(Value::as_str) {
+        Some(message) => entry.with_confirmation(message),
+        None => entry
+    }
+}
+
+impl View for DynamicView {
+    fn render(&mut self, disp: &mut Display) {
+        self.root.render(disp);
+    }
+
+    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        self.reload_if_changed();
+        self.root.handle_buttons(buttons)
+    }
+
+    fn activate(&mut self) {
+        self.root.activate();
+    }
+
+    fn set_ancestors(&mut self, ancestors: &[String]) {
+        self.root.set_ancestors(ancestors);
+    }
+
+    fn path(&self) -> Vec<String> {
+        self.root.path()
+    }
+
+    fn open_path(&mut self, path: &[String]) -> usize {
+        self.root.open_path(path)
     }
 }
-*/
@@ -5,8 +5,9 @@ use crate::{View, ReturnState, Display};
 /// An empty view that has no interactions and doesn't render anything.
 pub struct EmptyView { }
 impl EmptyView { pub fn new() -> EmptyView { EmptyView { } } }
+impl Default for EmptyView { fn default() -> EmptyView { EmptyView::new() } }
 impl View for EmptyView {
-    fn render(&mut self, disp: &mut Display) {}
-    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState { None }
+    fn render(&mut self, _disp: &mut Display) {}
+    fn handle_buttons(&mut self, _buttons: &mut ButtonSet) -> ReturnState { None }
 }
 
@@ -1,28 +1,35 @@
 extern crate embedded_graphics;
 
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fs::DirEntry;
 use std::path::{PathBuf};
+use std::rc::Rc;
 
 use crate::{
-    View, ReturnState, Display,
+    View, ReturnState, ReturnStateEnum::*, Display,
     buttons::ButtonSet,
     views::{MenuView,TextView},
-    views::menu::MenuEntry
+    views::menu::{MenuEntry, MenuPosition}
 };
 
+/// Menu positions of the directories visited by a tree of file views.
+type Positions = Rc<RefCell<HashMap<PathBuf, MenuPosition>>>;
+
 /// A view that shows a list of items in a folder.
+///
+/// The selected item in each directory is remembered when leaving it, and
+/// restored when it is opened again.
 pub struct FileView {
     path: PathBuf,
-    menu: MenuView
+    menu: MenuView,
+    positions: Positions
 }
 
 impl FileView {
     /// Create a new file view from a path.
     pub fn new(dir: &str) -> FileView {
-        FileView {
-            path: PathBuf::from(dir),
-            menu: MenuView::new()
-        }
+        FileView::from(PathBuf::from(dir))
     }
 
     pub fn get_name(&self) -> String {
@@ -36,7 +43,11 @@ impl View for FileView {
     }
 
     fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
-        self.menu.handle_buttons(buttons)
+        let state = self.menu.handle_buttons(buttons);
+        if let Some(Pop) = state {
+            self.positions.borrow_mut().insert(self.path.clone(), self.menu.position());
+        }
+        state
     }
 
     fn activate(&mut self) {
@@ -47,12 +58,33 @@ impl View for FileView {
                 let name = dir.file_name().to_str().unwrap().to_owned();
                 if let Ok(file_type) = dir.file_type() {
                     if file_type.is_dir() {
-                        return (name.clone(), Box::new(FileView::from(self.path.join(dir.path()))));
+                        let path = self.path.join(dir.path());
+                        let positions = self.positions.clone();
+                        return MenuEntry::lazy(&name, move || FileView {
+                            path: path.clone(),
+                            menu: MenuView::new(),
+                            positions: positions.clone()
+                        });
                     }
                 }
-                return (name.clone(), Box::new(TextView::new(name.as_ref())));
+                MenuEntry::lazy(&name.clone(), move || TextView::new(name.as_ref()))
             }).collect()
         );
+        if let Some(position) = self.positions.borrow().get(&self.path) {
+            self.menu.set_position(*position);
+        }
+    }
+
+    fn set_ancestors(&mut self, ancestors: &[String]) {
+        self.menu.set_ancestors(ancestors);
+    }
+
+    fn path(&self) -> Vec<String> {
+        self.menu.path()
+    }
+
+    fn open_path(&mut self, path: &[String]) -> usize {
+        self.menu.open_path(path)
     }
 }
 
@@ -61,7 +93,8 @@ impl From<PathBuf> for FileView {
     fn from(p: PathBuf) -> FileView {
         FileView {
             path: p,
-            menu: MenuView::new()
+            menu: MenuView::new(),
+            positions: Rc::new(RefCell::new(HashMap::new()))
         }
     }
 }
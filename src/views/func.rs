@@ -1,24 +1,115 @@
+use std::fmt::Display as Show;
+
 use crate::{
     View, ReturnState, ReturnStateEnum::*, Display,
     buttons::ButtonSet,
+    views::TextView
 };
 
-pub struct FuncView<'a> {
-    func: Box<&'a dyn Fn() -> ()>
+/// What a `FuncView` does after running its function.
+enum Outcome {
+    /// Give this state to the parent view, then leave.
+    Return(ReturnState),
+    /// Show a message until it is dismissed.
+    Show(TextView)
+}
+
+/// A view that runs a function when activated.
+///
+/// By default the view leaves straight away. Functions made with
+/// `FuncView::fallible` and `FuncView::reporting` show their error or message
+/// until it is dismissed with B; `with_message` shows a fixed message on
+/// success.
+/// ```
+/// # use piscreen::views::FuncView;
+/// let mut count = 0;
+/// FuncView::new(move || count += 1);
+/// FuncView::fallible(|| std::fs::remove_file("/tmp/cache"))
+///     .with_message("Cache cleared");
+/// FuncView::reporting(|| std::fs::read_to_string("/etc/hostname"));
+/// ```
+pub struct FuncView {
+    func: Box<dyn FnMut() -> Outcome>,
+    message: Option<String>,
+    outcome: Option<Outcome>
 }
 
-impl<'a> FuncView<'a> {
-    pub fn new(func: &'a dyn Fn() -> ()) -> FuncView<'a> {
+impl FuncView {
+    /// Create a view that runs `func` and leaves.
+    pub fn new<F: FnMut() + 'static>(mut func: F) -> FuncView {
+        FuncView::from_outcome(move || {
+            func();
+            Outcome::Return(Some(Pop))
+        })
+    }
+
+    /// Create a view that runs `func` and gives its return state to the
+    /// parent view. Returning `None` just leaves.
+    pub fn returning<F: FnMut() -> ReturnState + 'static>(mut func: F) -> FuncView {
+        FuncView::from_outcome(move || Outcome::Return(func()))
+    }
+
+    /// Create a view that runs `func`, showing the error if it fails.
+    pub fn fallible<E, F>(mut func: F) -> FuncView
+    where E: Show, F: FnMut() -> Result<(), E> + 'static {
+        FuncView::from_outcome(move || match func() {
+            Ok(()) => Outcome::Return(Some(Pop)),
+            Err(e) => FuncView::error(e)
+        })
+    }
+
+    /// Create a view that runs `func`, showing the message it returns, or the
+    /// error if it fails.
+    pub fn reporting<E, F>(mut func: F) -> FuncView
+    where E: Show, F: FnMut() -> Result<String, E> + 'static {
+        FuncView::from_outcome(move || match func() {
+            Ok(message) => Outcome::Show(TextView::new(&message)),
+            Err(e) => FuncView::error(e)
+        })
+    }
+
+    fn error<E: Show>(e: E) -> Outcome {
+        Outcome::Show(TextView::new(&format!("Error: {}", e)))
+    }
+
+    fn from_outcome<F: FnMut() -> Outcome + 'static>(func: F) -> FuncView {
         FuncView {
-            func: Box::new(func)
+            func: Box::new(func),
+            message: None,
+            outcome: None
         }
     }
+
+    /// Show `message` when the function succeeds without a message of its own.
+    pub fn with_message(mut self, message: &str) -> FuncView {
+        self.message = Some(message.to_owned());
+        self
+    }
 }
 
-impl<'a> View for FuncView<'a> {
-    fn render(&mut self, disp: &mut Display) {}
-    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState { Some(Pop) }
+impl View for FuncView {
+    fn render(&mut self, disp: &mut Display) {
+        if let Some(Outcome::Show(text)) = &mut self.outcome {
+            text.render(disp);
+        }
+    }
+
+    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        match self.outcome.take() {
+            Some(Outcome::Show(mut text)) => {
+                let state = text.handle_buttons(buttons);
+                self.outcome = Some(Outcome::Show(text));
+                state
+            }
+            Some(Outcome::Return(state)) => state.or(Some(Pop)),
+            None => Some(Pop)
+        }
+    }
+
     fn activate(&mut self) {
-        (self.func)()
+        self.outcome = match ((self.func)(), &self.message) {
+            (Outcome::Return(Some(Pop)), Some(message)) => Some(Outcome::Show(TextView::new(message))),
+            (outcome, _) => Some(outcome)
+        };
     }
 }
@@ -8,28 +8,332 @@ use embedded_graphics::primitives::Rect;
 use embedded_graphics::primitives::Line;
 use embedded_graphics::Drawing;
 
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::Rc;
+
 use crate::{
     buttons::ButtonSet,
-    views::{ON, OFF},
-    View, ReturnState, ReturnStateEnum::*, Display
+    views::{ON, OFF, ConfirmView},
+    views::text_input::InputTree,
+    View, ReturnState, ReturnStateEnum::*, Display, unread_notifications
 };
 
 /// Number of entries shown on the screen.
-const NUM_ENTRIES_SHOWN: u8 = 4;
+const NUM_ENTRIES_SHOWN: usize = 4;
+
+/// Maximum number of characters that fit on a single menu row.
+const ROW_CHARS: usize = 20;
+
+/// What happens when a menu entry is selected.
+enum EntryKind {
+    /// Open a child view.
+    View(Box<dyn View>),
+    /// Build a child view when selected, and drop it again when it pops.
+    Lazy(Box<dyn FnMut() -> Box<dyn View>>, Option<Box<dyn View>>),
+    /// Flip a boolean in place, calling the change callback if there is one.
+    Toggle(Rc<Cell<bool>>, Option<Box<dyn FnMut(bool)>>),
+    /// Call a function in place, given the name of the entry it acts on.
+    Action(Box<dyn FnMut(&str)>)
+}
+
+/// A single menu row: a text label and the view opened when it is selected.
+///
+/// An entry may also carry a value, which is computed every time the menu is
+/// rendered and drawn right-aligned on the row.
+pub struct MenuEntry {
+    name: String,
+    kind: EntryKind,
+    value: Option<Box<dyn Fn() -> String>>,
+    icon: Option<char>,
+    actions: Option<MenuView>,
+    confirm: Option<ConfirmView>
+}
+
+impl MenuEntry {
+    /// Create a new entry from a label and a view.
+    pub fn new(name: &str, view: Box<dyn View>) -> MenuEntry {
+        MenuEntry {
+            name: name.to_owned(),
+            kind: EntryKind::View(view),
+            value: None,
+            icon: None,
+            actions: None,
+            confirm: None
+        }
+    }
+
+    /// Create an entry whose view is only built by `factory` when the entry is
+    /// selected. The view is dropped once it pops back to the menu.
+    /// ```
+    /// # use piscreen::views::*;
+    /// MenuEntry::lazy("Log", || TextView::new(&std::fs::read_to_string("/var/log/syslog").unwrap()));
+    /// ```
+    pub fn lazy<V: View + 'static, F: FnMut() -> V + 'static>(name: &str, mut factory: F) -> MenuEntry {
+        MenuEntry {
+            name: name.to_owned(),
+            kind: EntryKind::Lazy(Box::new(move || Box::new(factory())), None),
+            value: None,
+            icon: None,
+            actions: None,
+            confirm: None
+        }
+    }
+
+    /// Create an entry that shows a checkbox and flips `state` when selected,
+    /// without leaving the menu.
+    /// ```
+    /// # use piscreen::views::*;
+    /// # use std::{rc::Rc, cell::Cell};
+    /// let bluetooth = Rc::new(Cell::new(false));
+    /// MenuEntry::toggle("Bluetooth", bluetooth.clone())
+    ///     .with_on_change(|on| println!("Bluetooth is now {}", on));
+    /// ```
+    pub fn toggle(name: &str, state: Rc<Cell<bool>>) -> MenuEntry {
+        MenuEntry {
+            name: name.to_owned(),
+            kind: EntryKind::Toggle(state, None),
+            value: None,
+            icon: None,
+            actions: None,
+            confirm: None
+        }
+    }
+
+    /// Create an entry that calls `action` when selected, without leaving the menu.
+    pub fn action<F: FnMut() + 'static>(name: &str, mut action: F) -> MenuEntry {
+        MenuEntry::context_action(name, move |_| action())
+    }
+
+    /// Create an entry that calls `action` when selected, without leaving the
+    /// menu. When it is a context action, `action` is given the name of the
+    /// entry that C was pressed on, and otherwise its own name.
+    pub fn context_action<F: FnMut(&str) + 'static>(name: &str, action: F) -> MenuEntry {
+        MenuEntry {
+            name: name.to_owned(),
+            kind: EntryKind::Action(Box::new(action)),
+            value: None,
+            icon: None,
+            actions: None,
+            confirm: None
+        }
+    }
+
+    /// Add an action to the popup menu shown when C is tapped on this entry.
+    /// The popup closes once the chosen action has finished.
+    /// ```
+    /// # use piscreen::views::*;
+    /// MenuEntry::new("notes.txt", Box::new(TextView::new("...")))
+    ///     .with_context_action(MenuEntry::context_action("Delete", |name| {
+    ///         std::fs::remove_file(name).unwrap();
+    ///     }))
+    ///     .with_context_action(MenuEntry::lazy("Info", || TextView::new("3 bytes")));
+    /// ```
+    pub fn with_context_action<E: Into<MenuEntry>>(mut self, action: E) -> MenuEntry {
+        let name = self.name.clone();
+        self.actions.get_or_insert_with(|| MenuView::popup(&name)).add_entry(action);
+        self
+    }
+
+    /// Ask `message` in a `ConfirmView` when the entry is selected, and only
+    /// open or run it if the answer is yes.
+    /// ```
+    /// # use piscreen::views::*;
+    /// MenuEntry::lazy("Reboot", || CommandView::new("sudo", &["reboot"]))
+    ///     .with_confirmation("Reboot now?");
+    /// ```
+    pub fn with_confirmation(mut self, message: &str) -> MenuEntry {
+        self.confirm = Some(ConfirmView::new(message));
+        self
+    }
+
+    /// Show a character before the label of the entry.
+    pub fn with_icon(mut self, icon: char) -> MenuEntry {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Call `on_change` with the new state whenever a toggle entry is flipped.
+    /// Has no effect on other entries.
+    pub fn with_on_change<F: FnMut(bool) + 'static>(mut self, on_change: F) -> MenuEntry {
+        if let EntryKind::Toggle(_, callback) = &mut self.kind {
+            *callback = Some(Box::new(on_change));
+        }
+        self
+    }
+
+    /// Show the result of `value` on the right side of the row.
+    /// ```
+    /// # use piscreen::views::*;
+    /// let volume = 70;
+    /// MenuEntry::new("Volume", Box::new(EmptyView::new()))
+    ///     .with_value(move || format!("{}%", volume));
+    /// ```
+    pub fn with_value<F: Fn() -> String + 'static>(mut self, value: F) -> MenuEntry {
+        self.value = Some(Box::new(value));
+        self
+    }
+
+    /// Show the current contents of a shared value on the right side of the row.
+    /// ```
+    /// # use piscreen::views::*;
+    /// # use std::{rc::Rc, cell::RefCell};
+    /// let wifi = Rc::new(RefCell::new("on"));
+    /// MenuEntry::new("Wi-Fi", Box::new(EmptyView::new()))
+    ///     .with_bound_value(wifi.clone());
+    /// *wifi.borrow_mut() = "off";
+    /// ```
+    pub fn with_bound_value<T: fmt::Display + 'static>(self, value: Rc<RefCell<T>>) -> MenuEntry {
+        self.with_value(move || value.borrow().to_string())
+    }
+
+    /// The label of the entry.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The current value of the entry, if it has one.
+    pub fn value(&self) -> Option<String> {
+        self.value.as_ref().map(|value| value())
+    }
+
+    /// The state of a toggle entry, or `None` if this is not a toggle.
+    pub fn toggle_state(&self) -> Option<bool> {
+        match &self.kind {
+            EntryKind::Toggle(state, _) => Some(state.get()),
+            _ => None
+        }
+    }
+
+    /// Select the entry, given the breadcrumb of the menu it is in and the
+    /// entry it acts on if it is a context action.
+    /// Returns whether a child view was opened.
+    fn activate(&mut self, breadcrumb: &[String], target: Option<&str>) -> bool {
+        let mut ancestors = breadcrumb.to_vec();
+        ancestors.push(self.name.clone());
+        match &mut self.kind {
+            EntryKind::View(view) => {
+                view.set_ancestors(&ancestors);
+                view.activate();
+                true
+            }
+            EntryKind::Lazy(factory, built) => {
+                let mut view = factory();
+                view.set_ancestors(&ancestors);
+                view.activate();
+                *built = Some(view);
+                true
+            }
+            EntryKind::Toggle(state, on_change) => {
+                state.set(!state.get());
+                if let Some(on_change) = on_change {
+                    on_change(state.get());
+                }
+                false
+            }
+            EntryKind::Action(action) => {
+                action(target.unwrap_or(&self.name));
+                false
+            }
+        }
+    }
+
+    /// Called when the child view of the entry pops back to the menu.
+    fn deactivate(&mut self) {
+        if let EntryKind::Lazy(_, built) = &mut self.kind {
+            *built = None;
+        }
+    }
+
+    /// Whether selecting the entry opens a child view.
+    fn opens_view(&self) -> bool {
+        matches!(self.kind, EntryKind::View(_) | EntryKind::Lazy(..))
+    }
+
+    /// The child view of the entry, if it has one.
+    fn view(&mut self) -> Option<&mut Box<dyn View>> {
+        match &mut self.kind {
+            EntryKind::View(view) => Some(view),
+            EntryKind::Lazy(_, built) => built.as_mut(),
+            _ => None
+        }
+    }
+}
 
-/// Type representing a text entry and a Viewable object.
-pub type MenuEntry = (String, Box<dyn View>);
+impl From<(String, Box<dyn View>)> for MenuEntry {
+    fn from(entry: (String, Box<dyn View>)) -> MenuEntry {
+        MenuEntry::new(&entry.0, entry.1)
+    }
+}
+
+/// Return at most the first `n` characters of a string.
+fn truncate(s: &str, n: usize) -> &str {
+    match s.char_indices().nth(n) {
+        Some((i, _)) => &s[..i],
+        None => s
+    }
+}
+
+/// The selected entry and scroll position of a `MenuView`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MenuPosition {
+    pub selected: usize,
+    pub first_visible_item: usize
+}
 
 /// A view that provies a scrolling list of selectable entries.
+///
+/// The footer shows the number of unread notifications, if there are any.
+/// A child view that returns `Text`, such as a value picked in a dialog, is
+/// closed just like one that pops.
+///
+/// Tapping C on an entry opens its context actions, if it has any. Holding C
+/// on any entry starts filtering the menu:
+/// each character picked narrows the list down to the entries whose names
+/// contain the filter, and B removes the last character again.
 pub struct MenuView {
     name: Option<String>,
+    ancestors: Vec<String>,
     active: bool,
+    context_open: bool,
+    confirm_open: bool,
+    is_popup: bool,
+    target: Option<String>,
     entries: Vec<MenuEntry>,
-    selected: u8,
-    first_visible_item: u8,
+    selected: usize,
+    first_visible_item: usize,
+    filter: Option<String>,
+    filter_input: Option<InputTree>,
     text_scroll_offset: u8
 }
 
+const ELLIPSIS: &[u8] = &[
+    0b00000000,
+    0b00000000,
+    0b00000000,
+    0b00000000,
+    0b00000000,
+    0b00000000,
+    0b10101000,
+    0b00000000,
+];
+
+/// Join a breadcrumb with slashes, dropping names from the left until it fits
+/// in `n` characters. Returns the text and whether anything was dropped.
+fn collapse_left(breadcrumb: &[String], n: usize) -> (String, bool) {
+    let text = breadcrumb.join("/");
+    let len = text.chars().count();
+    if len <= n {
+        return (text, false)
+    }
+    let fits = |s: &str| s.chars().count() < n;
+    let text = match text.match_indices('/').map(|(i, _)| &text[i..]).find(|s| fits(s)) {
+        Some(rest) => rest.to_owned(),
+        None => text.chars().skip(len + 1 - n).collect()
+    };
+    (text, true)
+}
+
 const ARROW_DOWN: &[u8] = &[
     0b00000000,
     0b00100000,
@@ -44,26 +348,84 @@ const ARROW_DOWN: &[u8] = &[
 impl MenuView {
     /// Create a new, empty menu.
     pub fn new() -> MenuView {
-        MenuView {
-            name: None,
-            entries: vec![],
-            selected: 0,
-            active: false,
-            first_visible_item: 0,
-            text_scroll_offset: 0
-        }
+        MenuView::with_entries(vec![])
+    }
+
+    /// Create a new, empty menu that pops as soon as one of its entries has
+    /// been selected and any view it opened has popped.
+    pub fn popup(name: &str) -> MenuView {
+        let mut menu = MenuView::new();
+        menu.set_name(name);
+        menu.is_popup = true;
+        menu
     }
 
     /// Add an entry to a menu.
-    pub fn add_entry(&mut self, entry: MenuEntry) {
-        self.entries.push(
-            (entry.0.to_owned(), entry.1)
-        );
+    pub fn add_entry<E: Into<MenuEntry>>(&mut self, entry: E) {
+        self.entries.push(entry.into());
     }
 
     /// Set the list of entries on an existing menu.
+    /// This clears any filter, and keeps the selected entry selected if there is
+    /// still one with the same name.
     pub fn set_entries(&mut self, entries: Vec<MenuEntry>) {
+        let selected = self.selected_entry().map(|entry| entry.name.clone());
         self.entries = entries;
+        self.clear_filter();
+        let found = match selected {
+            Some(name) => self.select_name(&name),
+            None => false
+        };
+        if !found && self.selected >= self.entries.len() {
+            self.first_entry();
+        }
+    }
+
+    /// Select the entry with the given name, clearing any filter.
+    /// Returns whether an entry was found.
+    pub fn select_name(&mut self, name: &str) -> bool {
+        self.clear_filter();
+        match self.entries.iter().position(|entry| entry.name == name) {
+            Some(index) => {
+                self.selected = index;
+                if self.selected < self.first_visible_item {
+                    self.first_visible_item = self.selected;
+                }
+                if self.selected >= self.first_visible_item + NUM_ENTRIES_SHOWN {
+                    self.first_visible_item = self.selected + 1 - NUM_ENTRIES_SHOWN;
+                }
+                true
+            }
+            None => false
+        }
+    }
+
+    /// The name of the selected entry, if any entries are shown.
+    pub fn selected_name(&self) -> Option<&str> {
+        let index = *self.shown().get(self.selected)?;
+        Some(&self.entries[index].name)
+    }
+
+    /// Get the selected entry and scroll position, e.g. to restore it later.
+    pub fn position(&self) -> MenuPosition {
+        MenuPosition {
+            selected: self.selected,
+            first_visible_item: self.first_visible_item
+        }
+    }
+
+    /// Restore a position returned by `position`, keeping it within the menu.
+    pub fn set_position(&mut self, position: MenuPosition) {
+        let len = self.shown().len();
+        if len == 0 {
+            self.first_entry();
+            return
+        }
+        self.selected = std::cmp::min(position.selected, len - 1);
+        self.first_visible_item = std::cmp::min(position.first_visible_item, self.selected);
+        if self.selected >= self.first_visible_item + NUM_ENTRIES_SHOWN {
+            self.first_visible_item = self.selected + 1 - NUM_ENTRIES_SHOWN;
+        }
     }
 
     /// Select the name of the menu
@@ -71,10 +433,58 @@ impl MenuView {
         self.name = Some(name.to_owned());
     }
 
+    /// The names leading to this menu, shown in its footer: the entries opened
+    /// to reach it, or its own name if it is the root menu.
+    pub fn breadcrumb(&self) -> Vec<String> {
+        match self.ancestors.is_empty() {
+            true => self.name.iter().cloned().collect(),
+            false => self.ancestors.clone()
+        }
+    }
+
+    /// Narrow the menu down to entries whose names contain `filter`, ignoring case.
+    pub fn set_filter(&mut self, filter: &str) {
+        self.filter = Some(filter.to_owned());
+        self.first_entry();
+    }
+
+    /// Show all entries again.
+    pub fn clear_filter(&mut self) {
+        self.filter = None;
+        self.filter_input = None;
+    }
+
+    /// The current filter, if the menu is being filtered.
+    pub fn filter(&self) -> Option<&str> {
+        self.filter.as_deref()
+    }
+
+    /// Indices of the entries that match the current filter.
+    fn shown(&self) -> Vec<usize> {
+        match &self.filter {
+            Some(filter) => {
+                let filter = filter.to_lowercase();
+                self.entries.iter().enumerate()
+                    .filter(|(_, entry)| entry.name.to_lowercase().contains(&filter))
+                    .map(|(i, _)| i)
+                    .collect()
+            }
+            None => (0..self.entries.len()).collect()
+        }
+    }
+
+    /// The currently selected entry, if any entries are shown.
+    fn selected_entry(&mut self) -> Option<&mut MenuEntry> {
+        let index = *self.shown().get(self.selected)?;
+        self.entries.get_mut(index)
+    }
+
     /// Select the next entry in the menu.
     pub fn next_entry(&mut self) {
+        let len = self.shown().len();
+        if len == 0 { return }
         self.selected += 1;
-        self.selected %= self.entries.len() as u8;
+        self.selected %= len;
         if self.selected == 0 {
             self.first_visible_item = 0;
         }
@@ -85,9 +495,11 @@ impl MenuView {
 
     /// Select the previous entry in the menu.
     pub fn prev_entry(&mut self) {
+        let len = self.shown().len();
+        if len == 0 { return }
         if self.selected == 0 {
-            self.selected = self.entries.len() as u8 - 1;
-            self.first_visible_item = self.entries.len() as u8 - NUM_ENTRIES_SHOWN;
+            self.selected = len - 1;
+            self.first_visible_item = len.saturating_sub(NUM_ENTRIES_SHOWN);
         } else {
             self.selected -= 1;
         }
@@ -104,8 +516,10 @@ impl MenuView {
 
     /// Select the last entry
     pub fn last_entry(&mut self) {
-        self.selected = self.entries.len() as u8 - 1;
-        if self.entries.len() > NUM_ENTRIES_SHOWN as usize {
+        let len = self.shown().len();
+        if len == 0 { return }
+        self.selected = len - 1;
+        if len > NUM_ENTRIES_SHOWN {
             self.first_visible_item = self.selected - (NUM_ENTRIES_SHOWN - 1);
         }
     }
@@ -114,14 +528,59 @@ impl MenuView {
     pub fn with_entries(entries: Vec<MenuEntry>) -> MenuView {
         MenuView {
             name: None,
+            ancestors: vec![],
             entries,
             selected: 0,
             active: false,
+            context_open: false,
+            confirm_open: false,
+            is_popup: false,
+            target: None,
             first_visible_item: 0,
+            filter: None,
+            filter_input: None,
             text_scroll_offset: 0
         }
     }
 
+    /// Select the selected entry, opening its view if it has one.
+    fn activate_selected(&mut self) -> ReturnState {
+        let breadcrumb = self.breadcrumb();
+        let target = self.target.clone();
+        if let Some(entry) = self.selected_entry() {
+            self.active = entry.activate(&breadcrumb, target.as_deref());
+            if self.is_popup && !self.active {
+                return Some(Pop)
+            }
+        }
+        None
+    }
+
+    /// Handle the buttons while a filter character is being picked.
+    fn handle_buttons_filter(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        if buttons.c.is_held() {
+            // Ignore the repeats of the hold that started filtering.
+            buttons.c.was_pressed();
+        }
+        let input = self.filter_input.as_mut().unwrap();
+        match input.handle_buttons(buttons) {
+            Some(Text(t)) => {
+                let mut filter = self.filter.take().unwrap_or_default();
+                filter.push_str(&t);
+                self.filter_input = None;
+                self.set_filter(&filter);
+            }
+            Some(Pop) => {
+                self.filter_input = None;
+                if self.filter() == Some("") {
+                    self.clear_filter();
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
     /// Handle the buttons from the menu itself (i.e. don't pass down to any children).
     fn handle_buttons_self(&mut self, buttons: &mut ButtonSet) -> ReturnState {
         if buttons.up.was_pressed() { self.prev_entry() }
@@ -131,13 +590,45 @@ impl MenuView {
         if buttons.right.was_pressed() { self.last_entry() }
 
         if buttons.a.was_pressed() {
-            self.active = true;
-            self.entries[self.selected as usize].1.activate();
+            match self.selected_entry().and_then(|entry| entry.confirm.as_mut()) {
+                Some(confirm) => {
+                    confirm.activate();
+                    self.confirm_open = true;
+                }
+                None => {
+                    let state = self.activate_selected();
+                    if state.is_some() {
+                        return state
+                    }
+                }
+            }
         }
         if buttons.b.was_pressed() {
-            return Some(Pop)
+            match self.filter.as_mut() {
+                Some(filter) => {
+                    filter.pop();
+                    if filter.is_empty() {
+                        self.clear_filter();
+                    }
+                    self.first_entry();
+                }
+                None => return Some(Pop)
+            }
+        }
+        if buttons.c.was_pressed() && buttons.c.is_held() {
+            if self.filter.is_none() {
+                self.filter = Some(String::new());
+            }
+            self.filter_input = Some(InputTree::new());
+        } else if buttons.c.was_tapped() {
+            let entry = self.selected_entry();
+            let target = entry.as_ref().map(|entry| entry.name.clone());
+            if let Some(actions) = entry.and_then(|entry| entry.actions.as_mut()) {
+                actions.first_entry();
+                actions.target = target;
+                self.context_open = true;
+            }
         }
-        if buttons.c.was_pressed() { }
         ReturnState::None
     }
 
@@ -149,27 +640,60 @@ impl MenuView {
             true => 123,
             false => 127,
         };
-        for (i, entry) in self.entries.iter().skip(self.first_visible_item as usize).take(NUM_ENTRIES_SHOWN as usize).enumerate() {
-            let is_selected = std::ptr::eq(&self.entries[self.selected as usize], entry);
-            disp.draw(Rect::new(
-                    Coord::new(0, i as i32 * 13),
-                    Coord::new(width, (i + 1) as i32 * 13))
-                .with_stroke(ON)
-                .with_fill(match is_selected { true => ON, false => OFF })
-                .into_iter());
-            disp.draw(Font6x8::render_str(match entry.0.get(..20) {
-                Some(s) => s,
-                None => entry.0.as_str()
-            })
-                .with_stroke(match !is_selected { true => ON, false => OFF })
-                .with_fill(match is_selected { true => ON, false => OFF })
-                .translate(Coord::new(3, i as i32 * 13 + 3))
-                .into_iter());
-            disp.draw(Rect::new(
-                    Coord::new(0, i as i32 * 13),
-                    Coord::new(width, (i + 1) as i32 * 13))
-                .with_stroke(ON)
-                .into_iter());
+        let shown = self.shown();
+        if let Some(input) = &self.filter_input {
+            input.render(disp);
+        } else {
+            for (i, &index) in shown.iter().skip(self.first_visible_item).take(NUM_ENTRIES_SHOWN).enumerate() {
+                let entry = &self.entries[index];
+                let is_selected = self.first_visible_item + i == self.selected;
+                disp.draw(Rect::new(
+                        Coord::new(0, i as i32 * 13),
+                        Coord::new(width, (i + 1) as i32 * 13))
+                    .with_stroke(ON)
+                    .with_fill(match is_selected { true => ON, false => OFF })
+                    .into_iter());
+                let value = entry.value();
+                let toggle_state = entry.toggle_state();
+                let label_chars = match (&value, toggle_state) {
+                    (Some(value), _) => ROW_CHARS.saturating_sub(value.chars().count() + 1),
+                    (None, Some(_)) => ROW_CHARS - 2,
+                    (None, None) => ROW_CHARS
+                };
+                let label = match entry.icon {
+                    Some(icon) => format!("{} {}", icon, entry.name),
+                    None => entry.name.clone()
+                };
+                disp.draw(Font6x8::render_str(truncate(&label, label_chars))
+                    .with_stroke(match !is_selected { true => ON, false => OFF })
+                    .with_fill(match is_selected { true => ON, false => OFF })
+                    .translate(Coord::new(3, i as i32 * 13 + 3))
+                    .into_iter());
+                if let Some(value) = &value {
+                    let value = truncate(value, ROW_CHARS);
+                    disp.draw(Font6x8::render_str(value)
+                        .with_stroke(match !is_selected { true => ON, false => OFF })
+                        .with_fill(match is_selected { true => ON, false => OFF })
+                        .translate(Coord::new(width - 1 - 6 * value.chars().count() as i32, i as i32 * 13 + 3))
+                        .into_iter());
+                } else if let Some(state) = toggle_state {
+                    let top_left = Coord::new(width - 10, i as i32 * 13 + 3);
+                    disp.draw(Rect::new(top_left, top_left + Coord::new(6, 6))
+                        .with_stroke(match !is_selected { true => ON, false => OFF })
+                        .into_iter());
+                    if state {
+                        disp.draw(Rect::new(top_left + Coord::new(2, 2), top_left + Coord::new(4, 4))
+                            .with_stroke(match !is_selected { true => ON, false => OFF })
+                            .with_fill(match !is_selected { true => ON, false => OFF })
+                            .into_iter());
+                    }
+                }
+                disp.draw(Rect::new(
+                        Coord::new(0, i as i32 * 13),
+                        Coord::new(width, (i + 1) as i32 * 13))
+                    .with_stroke(ON)
+                    .into_iter());
+            }
         }
         if has_scroll {
             disp.draw(Line::new(
@@ -187,50 +711,173 @@ impl MenuView {
                     Coord::new(127 - 1, 63))
                 .with_stroke(ON)
                 .into_iter());
-            let height = 60f32 / self.entries.len() as f32;
-            let offset = (2f32 + self.selected as f32 * height as f32) as u8;
+            let height = 60f32 / shown.len() as f32;
+            let offset = (2f32 + self.selected as f32 * height) as u8;
             disp.draw(Rect::new(
                     Coord::new(127 - 2, offset as i32),
                     Coord::new(127, (offset + height as u8) as i32))
                 .with_fill(ON)
                 .into_iter());
-        } else {
-
         }
-        match &self.name {
-            Some(name) => disp.draw(Font6x8::render_str(name.as_ref())
-                .translate(Coord::new(3, 4 * 13 + 3))
-                .into_iter()),
-            None => {}
+        let has_more = self.filter_input.is_none() && NUM_ENTRIES_SHOWN <= shown.len()
+            && self.first_visible_item < shown.len() - NUM_ENTRIES_SHOWN;
+        let badge = match unread_notifications() {
+            0 => None,
+            unread @ 1..=99 => Some(unread.to_string()),
+            _ => Some("99+".to_owned())
+        };
+        let badge_chars = badge.as_ref().map_or(0, |badge| badge.len() + 1);
+        let footer_chars = match has_more {
+            true => ROW_CHARS - 2 - badge_chars,
+            false => ROW_CHARS - badge_chars
         };
-        if self.first_visible_item < self.entries.len() as u8 - NUM_ENTRIES_SHOWN
-            && NUM_ENTRIES_SHOWN <= self.entries.len() as u8 {
+        let (footer, collapsed) = match (&self.filter, &self.filter_input) {
+            (Some(filter), Some(_)) => (format!("/{}_", filter), false),
+            (Some(filter), None) => (format!("/{}", filter), false),
+            (None, _) => collapse_left(&self.breadcrumb(), footer_chars)
+        };
+        let mut footer_x = 3;
+        if collapsed {
+            disp.draw(Image1BPP::new(ELLIPSIS, 6, 8)
+                .translate(Coord::new(footer_x, 4 * 13 + 3))
+                .into_iter());
+            footer_x += 6;
+        }
+        disp.draw(Font6x8::render_str(truncate(&footer, footer_chars))
+            .translate(Coord::new(footer_x, 4 * 13 + 3))
+            .into_iter());
+        if has_more {
             disp.draw(Image1BPP::new(ARROW_DOWN, 5, 8)
                 .translate(Coord::new(width - 7, 4 * 13 + 3))
                 .into_iter());
         }
+        if let Some(badge) = badge {
+            let right = match has_more {
+                true => width - 9,
+                false => width - 1
+            };
+            let left = right - 6 * badge.len() as i32 - 2;
+            disp.draw(Rect::new(Coord::new(left, 4 * 13 + 2), Coord::new(right, 4 * 13 + 11))
+                .with_fill(ON)
+                .into_iter());
+            disp.draw(Font6x8::render_str(&badge)
+                .with_stroke(OFF)
+                .with_fill(ON)
+                .translate(Coord::new(left + 2, 4 * 13 + 3))
+                .into_iter());
+        }
         self.text_scroll_offset = 0;
     }
 }
 
+impl Default for MenuView {
+    fn default() -> MenuView { MenuView::new() }
+}
+
 impl View for MenuView {
     fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
-        if self.active {
-            match self.entries[self.selected as usize].1.handle_buttons(buttons) {
-                Some(Pop) => {
+        if self.context_open {
+            let actions = self.selected_entry().unwrap().actions.as_mut().unwrap();
+            if let Some(Pop) = actions.handle_buttons(buttons) {
+                self.context_open = false;
+            }
+            None
+        } else if self.confirm_open {
+            let confirm = self.selected_entry().unwrap().confirm.as_mut().unwrap();
+            match confirm.handle_buttons(buttons) {
+                Some(_) if confirm.is_confirmed() => {
+                    self.confirm_open = false;
+                    self.activate_selected()
+                }
+                Some(_) => {
+                    self.confirm_open = false;
+                    None
+                }
+                None => None
+            }
+        } else if self.active {
+            let entry = self.selected_entry().unwrap();
+            match entry.view().map(|view| view.handle_buttons(buttons)) {
+                Some(Some(Pop)) | Some(Some(Text(_))) => {
+                    entry.deactivate();
                     self.active = false;
+                    if self.is_popup {
+                        return Some(Pop)
+                    }
                     None
                 },
                 _ => None
             }
+        } else if self.filter_input.is_some() {
+            self.handle_buttons_filter(buttons)
         } else {
             self.handle_buttons_self(buttons)
         }
     }
 
+    fn set_ancestors(&mut self, ancestors: &[String]) {
+        self.ancestors = ancestors.to_vec();
+    }
+
+    fn path(&self) -> Vec<String> {
+        if !self.active {
+            return vec![]
+        }
+        let index = self.shown()[self.selected];
+        let entry = &self.entries[index];
+        let mut path = vec![entry.name.clone()];
+        if let EntryKind::View(view) | EntryKind::Lazy(_, Some(view)) = &entry.kind {
+            path.extend(view.path());
+        }
+        path
+    }
+
+    fn open_path(&mut self, path: &[String]) -> usize {
+        let name = match path.first() {
+            Some(name) => name,
+            None => return 0
+        };
+        if self.active && self.selected_name() != Some(name) {
+            self.selected_entry().unwrap().deactivate();
+            self.active = false;
+        }
+        self.context_open = false;
+        self.confirm_open = false;
+        if !self.active {
+            if !self.select_name(name) {
+                return 0
+            }
+            let entry = self.selected_entry().unwrap();
+            if !entry.opens_view() {
+                return 0
+            }
+            if let Some(confirm) = entry.confirm.as_mut() {
+                // Ask first, as when A is pressed. The entry's view is only
+                // opened once confirmed, so the rest of the path is not.
+                confirm.activate();
+                self.confirm_open = true;
+                return 1
+            }
+            let breadcrumb = self.breadcrumb();
+            let target = self.target.clone();
+            self.active = self.selected_entry().unwrap().activate(&breadcrumb, target.as_deref());
+        }
+        1 + self.selected_entry().unwrap().view().unwrap().open_path(&path[1..])
+    }
+
     fn render(&mut self, disp: &mut Display) {
+        if self.context_open {
+            self.selected_entry().unwrap().actions.as_mut().unwrap().render(disp);
+            return
+        }
+        if self.confirm_open {
+            self.selected_entry().unwrap().confirm.as_mut().unwrap().render(disp);
+            return
+        }
         match self.active {
-            true => self.entries[self.selected as usize].1.render(disp),
+            true => if let Some(view) = self.selected_entry().unwrap().view() {
+                view.render(disp)
+            },
             false => self.render_self(disp)
         }
     }
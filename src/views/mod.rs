@@ -5,6 +5,17 @@ mod text_input;
 mod empty;
 mod file;
 mod func;
+mod command;
+mod stream;
+mod script;
+mod task;
+mod confirm;
+mod notifications;
+mod progress;
+mod number;
+mod slider;
+mod radio;
+mod checklist;
 
 use embedded_graphics::pixelcolor::PixelColorU8;
 
@@ -13,9 +24,28 @@ pub const OFF: Option<PixelColorU8> = Some(PixelColorU8(0));
 
 pub use menu::MenuView;
 pub use menu::MenuEntry;
+pub use menu::MenuPosition;
 pub use text::TextView;
+pub(crate) use text::wrap;
 pub use text_input::TextInputView;
 pub use empty::EmptyView;
 pub use file::FileView;
 pub use func::FuncView;
+pub use command::CommandView;
+pub use stream::StreamView;
+pub use script::ScriptView;
+pub use task::TaskView;
+pub use task::Progress;
+pub use confirm::ConfirmView;
+pub use notifications::NotificationView;
+pub use progress::ProgressView;
+pub use progress::ProgressHandle;
+pub use number::NumberView;
+pub use number::step_decimals;
+pub use slider::SliderView;
+pub use radio::RadioView;
+pub use checklist::ChecklistView;
 pub use dynamic::DynamicView;
+pub use dynamic::ConfigError;
+pub use dynamic::ConfigProblem;
+pub use dynamic::validate;
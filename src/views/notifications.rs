@@ -0,0 +1,111 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use crate::{
+    View, ReturnState, Display,
+    buttons::ButtonSet,
+    notification::{notifications, mark_read, dismiss, dismiss_all, Notification, Priority},
+    views::{MenuView, MenuEntry, TextView}
+};
+
+/// How long ago `time` was, in the largest whole unit, e.g. `5m`.
+fn age(time: SystemTime) -> String {
+    let secs = SystemTime::now().duration_since(time).unwrap_or_default().as_secs();
+    match secs {
+        0..=59 => "now".to_owned(),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400)
+    }
+}
+
+/// A menu of the notifications in the notification center.
+///
+/// Unread notifications are marked with `*`, and high priority ones with `!`.
+/// Selecting a notification marks it as read and shows its full text. Tapping
+/// C on a notification opens actions to dismiss it or every notification, and
+/// holding C filters the list.
+pub struct NotificationView {
+    menu: MenuView,
+    changed: Rc<Cell<bool>>
+}
+
+impl NotificationView {
+    pub fn new() -> NotificationView {
+        let mut menu = MenuView::new();
+        menu.set_name("Notifications");
+        NotificationView {
+            menu,
+            changed: Rc::new(Cell::new(false))
+        }
+    }
+
+    /// Create the menu entry for a notification.
+    fn entry(&self, notification: Notification) -> MenuEntry {
+        let Notification { id, priority, title, text, time, read } = notification;
+        let changed = self.changed.clone();
+        let changed_all = self.changed.clone();
+        let open = move || {
+            mark_read(id);
+            TextView::new(&text)
+        };
+        let entry = MenuEntry::lazy(&title, open)
+            .with_value(move || age(time))
+            .with_context_action(MenuEntry::action("Dismiss", move || {
+                dismiss(id);
+                changed.set(true);
+            }))
+            .with_context_action(MenuEntry::action("Dismiss all", move || {
+                dismiss_all();
+                changed_all.set(true);
+            }));
+        match (priority, read) {
+            (Priority::High, _) => entry.with_icon('!'),
+            (_, false) => entry.with_icon('*'),
+            (_, true) => entry
+        }
+    }
+
+    /// Rebuild the menu from the notification center.
+    fn refresh(&mut self) {
+        let entries = notifications().into_iter().map(|n| self.entry(n)).collect();
+        self.menu.set_entries(entries);
+        self.changed.set(false);
+    }
+}
+
+impl Default for NotificationView {
+    fn default() -> NotificationView { NotificationView::new() }
+}
+
+impl View for NotificationView {
+    fn render(&mut self, disp: &mut Display) {
+        self.menu.render(disp);
+    }
+
+    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        let was_open = !self.menu.path().is_empty();
+        let state = self.menu.handle_buttons(buttons);
+        if (was_open && self.menu.path().is_empty()) || self.changed.get() {
+            self.refresh();
+        }
+        state
+    }
+
+    fn activate(&mut self) {
+        self.refresh();
+    }
+
+    fn set_ancestors(&mut self, ancestors: &[String]) {
+        self.menu.set_ancestors(ancestors);
+    }
+
+    fn path(&self) -> Vec<String> {
+        self.menu.path()
+    }
+
+    fn open_path(&mut self, path: &[String]) -> usize {
+        self.menu.open_path(path)
+    }
+}
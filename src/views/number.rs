@@ -0,0 +1,192 @@
+use embedded_graphics::fonts::{Font,Font6x8,Font12x16};
+use embedded_graphics::coord::Coord;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rect;
+use embedded_graphics::Drawing;
+
+use crate::{
+    buttons::ButtonSet,
+    views::ON,
+    View, ReturnState, ReturnStateEnum::*, Display
+};
+
+/// Number of repeats of a held button before each tenfold speed up.
+const ACCELERATE_TICKS: u32 = 5;
+/// Number of characters of the value that fit across the screen.
+const VALUE_CHARS: usize = 10;
+
+/// Number of decimal places needed to show multiples of `step`, counted from
+/// the shortest way of writing it.
+/// ```
+/// # use piscreen::views::step_decimals;
+/// assert_eq!(step_decimals(1.0), 0);
+/// assert_eq!(step_decimals(0.25), 2);
+/// assert_eq!(step_decimals(2.5), 1);
+/// ```
+pub fn step_decimals(step: f64) -> usize {
+    match step.is_finite() {
+        true => step.to_string().split_once('.').map_or(0, |(_, decimals)| decimals.len()),
+        false => 0
+    }
+}
+
+/// A view for picking a number between a minimum and a maximum.
+///
+/// Up and down change the value by the step, going faster the longer they are
+/// held. Left and right make the step ten times larger or smaller. A accepts
+/// the value, calling the accept callback and returning it to the parent view
+/// as `Text`; B leaves without changing it.
+/// ```
+/// # use piscreen::views::NumberView;
+/// NumberView::new("Brightness", 0.0, 100.0, 1.0)
+///     .with_value(70.0)
+///     .with_unit("%")
+///     .with_on_accept(|value| println!("Brightness is now {}", value));
+/// ```
+pub struct NumberView {
+    name: String,
+    min: f64,
+    max: f64,
+    step: f64,
+    unit: String,
+    value: f64,
+    editing: f64,
+    magnitude: i32,
+    held_ticks: u32,
+    on_accept: Option<Box<dyn FnMut(f64)>>
+}
+
+impl NumberView {
+    /// Create a view for picking a multiple of `step` from `min` to `max`.
+    pub fn new(name: &str, min: f64, max: f64, step: f64) -> NumberView {
+        NumberView {
+            name: name.to_owned(),
+            min,
+            max: max.max(min),
+            step: step.abs(),
+            unit: String::new(),
+            value: min,
+            editing: min,
+            magnitude: 0,
+            held_ticks: 0,
+            on_accept: None
+        }
+    }
+
+    /// Start with `value`, kept within the range.
+    pub fn with_value(mut self, value: f64) -> NumberView {
+        self.value = value.clamp(self.min, self.max);
+        self.editing = self.value;
+        self
+    }
+
+    /// Show `unit` after the value, e.g. `%` or ` ms`.
+    pub fn with_unit(mut self, unit: &str) -> NumberView {
+        self.unit = unit.to_owned();
+        self
+    }
+
+    /// Call `on_accept` with the value whenever it is accepted with A.
+    pub fn with_on_accept<F: FnMut(f64) + 'static>(mut self, on_accept: F) -> NumberView {
+        self.on_accept = Some(Box::new(on_accept));
+        self
+    }
+
+    /// The last accepted value.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Format a value with as many decimal places as the step needs.
+    fn format(&self, value: f64) -> String {
+        format!("{:.*}", step_decimals(self.step), value)
+    }
+
+    /// The step after changing its magnitude with left and right.
+    fn step_size(&self) -> f64 {
+        self.step * 10f64.powi(self.magnitude)
+    }
+
+    /// Whether the step can be made ten times larger and still fit the range.
+    fn can_grow(&self, magnitude: i32) -> bool {
+        self.step > 0.0 && self.step * 10f64.powi(magnitude + 1) <= self.max - self.min
+    }
+
+    /// Move the value by `steps` steps, keeping it a multiple of the step
+    /// from the minimum and within the range.
+    fn adjust(&mut self, steps: f64, held: bool) {
+        self.held_ticks = match held {
+            true => self.held_ticks + 1,
+            false => 0
+        };
+        let mut magnitude = self.magnitude;
+        for _ in 0..self.held_ticks / ACCELERATE_TICKS {
+            if self.can_grow(magnitude) {
+                magnitude += 1;
+            }
+        }
+        let size = self.step * 10f64.powi(magnitude);
+        let value = self.editing + steps * size;
+        let value = match self.step > 0.0 {
+            true => self.min + ((value - self.min) / self.step).round() * self.step,
+            false => value
+        };
+        self.editing = value.clamp(self.min, self.max);
+    }
+}
+
+impl View for NumberView {
+    fn render(&mut self, disp: &mut Display) {
+        disp.draw(Font6x8::render_str(&self.name)
+            .translate(Coord::new(3, 3))
+            .into_iter());
+        let value: String = format!("{}{}", self.format(self.editing), self.unit)
+            .chars().take(VALUE_CHARS).collect();
+        let x = (128 - 12 * value.chars().count() as i32) / 2;
+        disp.draw(Font12x16::render_str(&value)
+            .translate(Coord::new(x, 20))
+            .into_iter());
+        disp.draw(Font6x8::render_str(&format!("Step {}", self.format(self.step_size())))
+            .translate(Coord::new(3, 44))
+            .into_iter());
+        let range = format!("{}-{}", self.format(self.min), self.format(self.max));
+        disp.draw(Font6x8::render_str(&range)
+            .translate(Coord::new(124 - 6 * range.chars().count() as i32, 53))
+            .into_iter());
+        disp.draw(Rect::new(Coord::new(0, 0), Coord::new(127, 63))
+            .with_stroke(ON)
+            .into_iter());
+    }
+
+    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        if buttons.up.was_pressed() {
+            self.adjust(1.0, buttons.up.is_held());
+        }
+        if buttons.down.was_pressed() {
+            self.adjust(-1.0, buttons.down.is_held());
+        }
+        if buttons.left.was_pressed() && self.can_grow(self.magnitude) {
+            self.magnitude += 1;
+        }
+        if buttons.right.was_pressed() && self.magnitude > 0 {
+            self.magnitude -= 1;
+        }
+        if buttons.a.was_pressed() {
+            self.value = self.editing;
+            if let Some(on_accept) = &mut self.on_accept {
+                on_accept(self.value);
+            }
+            return Some(Text(self.format(self.value)))
+        }
+        if buttons.b.was_pressed() {
+            return Some(Pop)
+        }
+        None
+    }
+
+    fn activate(&mut self) {
+        self.editing = self.value;
+        self.magnitude = 0;
+        self.held_ticks = 0;
+    }
+}
@@ -0,0 +1,216 @@
+use embedded_graphics::fonts::{Font,Font6x8};
+use embedded_graphics::coord::Coord;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rect;
+use embedded_graphics::Drawing;
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use crate::{
+    buttons::ButtonSet,
+    views::{text::wrap, TextView, ON},
+    View, ReturnState, ReturnStateEnum::*, Display
+};
+
+/// Characters cycled through by a spinner while work runs.
+const SPINNER: &[&str] = &["|", "/", "-", "\\"];
+/// How long each spinner character is shown for, in milliseconds.
+const SPINNER_DURATION: u128 = 150;
+/// Number of status lines shown.
+const STATUS_LINES: usize = 2;
+
+struct ProgressState {
+    fraction: Option<f32>,
+    status: String,
+    /// Set once the work is done, with the message to show if there is one.
+    done: Option<Option<String>>,
+    /// When the fraction was first set and what it was set to, used to
+    /// estimate the time left.
+    measuring_since: Option<(SystemTime, f32)>
+}
+
+/// Handle for updating a `ProgressView`, which can be cloned and sent to
+/// other threads.
+#[derive(Clone)]
+pub struct ProgressHandle {
+    state: Arc<Mutex<ProgressState>>
+}
+
+impl ProgressHandle {
+    /// Set how much of the work is done, from 0.0 to 1.0.
+    pub fn set_fraction(&self, fraction: f32) {
+        let mut state = self.state.lock().unwrap();
+        let fraction = fraction.clamp(0.0, 1.0);
+        state.fraction = Some(fraction);
+        state.measuring_since.get_or_insert_with(|| (SystemTime::now(), fraction));
+    }
+
+    /// Show a spinner instead of a bar, when it is not known how much is done.
+    pub fn set_indeterminate(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.fraction = None;
+        state.measuring_since = None;
+    }
+
+    /// Show what the work is doing.
+    pub fn set_status(&self, status: &str) {
+        self.state.lock().unwrap().status = status.to_owned();
+    }
+
+    /// Mark the work as done, leaving the view.
+    pub fn finish(&self) {
+        self.state.lock().unwrap().done = Some(None);
+    }
+
+    /// Mark the work as done, showing `message` until it is dismissed.
+    pub fn finish_with_message(&self, message: &str) {
+        self.state.lock().unwrap().done = Some(Some(message.to_owned()));
+    }
+}
+
+/// The spinner character to show after `elapsed`.
+pub(crate) fn spinner(elapsed: Duration) -> &'static str {
+    SPINNER[(elapsed.as_millis() / SPINNER_DURATION) as usize % SPINNER.len()]
+}
+
+/// Format a duration as e.g. `1h02m`, `3m05s` or `12s`.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    }
+}
+
+/// A view showing the progress of some work: a title, a status line and a
+/// bar, or a spinner while the amount done is unknown.
+///
+/// It is updated through a `ProgressHandle`, from the code that owns the view
+/// or from a background thread. Once the work finishes the view leaves, or
+/// shows a completion message. B leaves the view without stopping the work.
+/// Reopening the view starts it afresh, so it can be reused for new work.
+/// ```
+/// # use piscreen::views::ProgressView;
+/// let view = ProgressView::new("Downloading").with_eta();
+/// let progress = view.handle();
+/// std::thread::spawn(move || {
+///     for i in 0..=100 {
+///         progress.set_status(&format!("{} of 100 MB", i));
+///         progress.set_fraction(i as f32 / 100.0);
+///     }
+///     progress.finish_with_message("Download complete");
+/// });
+/// ```
+pub struct ProgressView {
+    title: String,
+    show_eta: bool,
+    state: Arc<Mutex<ProgressState>>,
+    started: SystemTime,
+    message: Option<TextView>
+}
+
+impl ProgressView {
+    /// Create an indeterminate progress view showing `title`.
+    pub fn new(title: &str) -> ProgressView {
+        ProgressView {
+            title: title.to_owned(),
+            show_eta: false,
+            state: Arc::new(Mutex::new(ProgressState {
+                fraction: None,
+                status: String::new(),
+                done: None,
+                measuring_since: None
+            })),
+            started: SystemTime::now(),
+            message: None
+        }
+    }
+
+    /// Show an estimate of the time left while the fraction done is known.
+    pub fn with_eta(mut self) -> ProgressView {
+        self.show_eta = true;
+        self
+    }
+
+    /// Get a handle for updating the view.
+    pub fn handle(&self) -> ProgressHandle {
+        ProgressHandle { state: self.state.clone() }
+    }
+
+    /// Whether the work has been marked as done.
+    pub fn is_done(&self) -> bool {
+        self.state.lock().unwrap().done.is_some()
+    }
+}
+
+impl View for ProgressView {
+    fn render(&mut self, disp: &mut Display) {
+        if let Some(message) = &mut self.message {
+            message.render(disp);
+            return
+        }
+        let state = self.state.lock().unwrap();
+        let now = SystemTime::now();
+        let title = match state.fraction {
+            Some(fraction) => format!("{} {:.0}%", self.title, fraction * 100.0),
+            None => {
+                format!("{} {}", self.title, spinner(now.duration_since(self.started).unwrap_or_default()))
+            }
+        };
+        disp.draw(Font6x8::render_str(&title)
+            .translate(Coord::new(3, 3))
+            .into_iter());
+        let mut lines: Vec<String> = wrap(&state.status).into_iter().take(STATUS_LINES).collect();
+        if let (true, Some(fraction), Some((since, start))) = (self.show_eta, state.fraction, state.measuring_since) {
+            if fraction > start && fraction < 1.0 {
+                let elapsed = now.duration_since(since).unwrap_or_default();
+                let left = elapsed.mul_f32((1.0 - fraction) / (fraction - start));
+                lines.push(format!("ETA {}", format_duration(left)));
+            }
+        }
+        for (i, line) in lines.iter().enumerate() {
+            disp.draw(Font6x8::render_str(line)
+                .translate(Coord::new(3, 14 + 9 * i as i32))
+                .into_iter());
+        }
+        if let Some(fraction) = state.fraction {
+            disp.draw(Rect::new(Coord::new(3, 47), Coord::new(124, 57))
+                .with_stroke(ON)
+                .into_iter());
+            let width = (118.0 * fraction) as i32;
+            if width > 0 {
+                disp.draw(Rect::new(Coord::new(5, 49), Coord::new(5 + width, 55))
+                    .with_fill(ON)
+                    .into_iter());
+            }
+        }
+        disp.draw(Rect::new(Coord::new(0, 0), Coord::new(127, 63))
+            .with_stroke(ON)
+            .into_iter());
+    }
+
+    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        if let Some(message) = &mut self.message {
+            return message.handle_buttons(buttons)
+        }
+        let done = self.state.lock().unwrap().done.clone();
+        match done {
+            Some(Some(message)) => self.message = Some(TextView::new(&message)),
+            Some(None) => return Some(Pop),
+            None => if buttons.b.was_pressed() { return Some(Pop) }
+        }
+        None
+    }
+
+    fn activate(&mut self) {
+        self.started = SystemTime::now();
+        self.message = None;
+        let mut state = self.state.lock().unwrap();
+        state.fraction = None;
+        state.status.clear();
+        state.done = None;
+        state.measuring_since = None;
+    }
+}
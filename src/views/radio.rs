@@ -0,0 +1,126 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::{
+    View, ReturnState, ReturnStateEnum::*, Display,
+    buttons::ButtonSet,
+    views::{MenuView, MenuEntry, MenuPosition}
+};
+
+/// Callback for a change of choice, shared with the menu entries.
+type OnChange = Rc<RefCell<Option<Box<dyn FnMut(usize, &str)>>>>;
+
+/// A list of options that one can be chosen from, drawn like a menu with the
+/// current choice marked with `*`.
+///
+/// A chooses the selected option, updating the shared choice, calling the
+/// change callback and returning the option to the parent view as `Text`. B
+/// leaves without changing the choice.
+///
+/// Added to a menu, it becomes an entry showing the current choice as its
+/// value.
+/// ```
+/// # use piscreen::views::*;
+/// # use std::{rc::Rc, cell::Cell};
+/// let rotation = Rc::new(Cell::new(0));
+/// let mut menu = MenuView::new();
+/// menu.add_entry(RadioView::new("Rotation", &["0", "90", "180", "270"], rotation.clone())
+///     .with_on_change(|_, degrees| println!("Rotating to {}", degrees)));
+/// ```
+pub struct RadioView {
+    name: String,
+    options: Rc<Vec<String>>,
+    choice: Rc<Cell<usize>>,
+    chosen: Rc<Cell<bool>>,
+    on_change: OnChange,
+    menu: MenuView
+}
+
+impl RadioView {
+    /// Create a list of `options`, with the index of the current one in `choice`.
+    pub fn new(name: &str, options: &[&str], choice: Rc<Cell<usize>>) -> RadioView {
+        RadioView {
+            name: name.to_owned(),
+            options: Rc::new(options.iter().map(|option| (*option).to_owned()).collect()),
+            choice,
+            chosen: Rc::new(Cell::new(false)),
+            on_change: Rc::new(RefCell::new(None)),
+            menu: MenuView::popup(name)
+        }
+    }
+
+    /// Call `on_change` with the index and option whenever a different option
+    /// is chosen.
+    pub fn with_on_change<F: FnMut(usize, &str) + 'static>(self, on_change: F) -> RadioView {
+        *self.on_change.borrow_mut() = Some(Box::new(on_change));
+        self
+    }
+
+    /// The index of the current choice.
+    pub fn choice(&self) -> usize {
+        self.choice.get()
+    }
+
+    /// The current choice, if its index is one of the options.
+    pub fn chosen_option(&self) -> Option<&str> {
+        self.options.get(self.choice.get()).map(String::as_str)
+    }
+
+    /// Rebuild the menu with the current choice marked.
+    fn refresh(&mut self) {
+        let entries = self.options.iter().enumerate().map(|(i, option)| {
+            let choice = self.choice.clone();
+            let chosen = self.chosen.clone();
+            let on_change = self.on_change.clone();
+            let options = self.options.clone();
+            MenuEntry::action(option, move || {
+                chosen.set(true);
+                if choice.get() != i {
+                    choice.set(i);
+                    if let Some(on_change) = on_change.borrow_mut().as_mut() {
+                        on_change(i, &options[i]);
+                    }
+                }
+            }).with_icon(match i == self.choice.get() { true => '*', false => ' ' })
+        }).collect();
+        self.menu.set_entries(entries);
+    }
+}
+
+impl View for RadioView {
+    fn render(&mut self, disp: &mut Display) {
+        self.menu.render(disp);
+    }
+
+    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        match self.menu.handle_buttons(buttons) {
+            Some(Pop) if self.chosen.get() => {
+                self.chosen.set(false);
+                self.refresh();
+                Some(Text(self.chosen_option().unwrap_or_default().to_owned()))
+            }
+            state => state
+        }
+    }
+
+    fn activate(&mut self) {
+        self.chosen.set(false);
+        self.refresh();
+        let choice = self.choice.get();
+        self.menu.set_position(MenuPosition { selected: choice, first_visible_item: 0 });
+    }
+
+    fn set_ancestors(&mut self, ancestors: &[String]) {
+        self.menu.set_ancestors(ancestors);
+    }
+}
+
+impl From<RadioView> for MenuEntry {
+    fn from(radio: RadioView) -> MenuEntry {
+        let choice = radio.choice.clone();
+        let options = radio.options.clone();
+        let name = radio.name.clone();
+        MenuEntry::new(&name, Box::new(radio))
+            .with_value(move || options.get(choice.get()).cloned().unwrap_or_default())
+    }
+}
@@ -0,0 +1,155 @@
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::{
+    View, ReturnState, Display,
+    buttons::ButtonSet,
+    views::{MenuView, MenuEntry, CommandView}
+};
+
+/// Number of lines at the top of a script searched for header comments.
+const HEADER_LINES: usize = 20;
+
+/// How a script is shown in a `ScriptView`.
+struct ScriptInfo {
+    name: String,
+    icon: Option<char>,
+    confirm: bool
+}
+
+impl ScriptInfo {
+    /// Read the details of a script from its header comments and sidecar file.
+    fn read(path: &Path) -> ScriptInfo {
+        let mut info = ScriptInfo {
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            icon: None,
+            confirm: false
+        };
+        if let Ok(file) = File::open(path) {
+            let lines = BufReader::new(file).lines().take(HEADER_LINES).map_while(Result::ok);
+            for line in lines {
+                if let Some((key, value)) = line.strip_prefix('#')
+                    .and_then(|comment| comment.trim().strip_prefix("piscreen-"))
+                    .and_then(|field| field.split_once(':')) {
+                    info.set(key.trim(), &Value::String(value.trim().to_owned()));
+                }
+            }
+        }
+        let sidecar = PathBuf::from(format!("{}.json", path.display()));
+        if let Ok(Value::Object(fields)) = fs::read_to_string(sidecar).map(|json| {
+            serde_json::from_str(&json).unwrap_or(Value::Null)
+        }) {
+            for (key, value) in fields.iter() {
+                info.set(key, value);
+            }
+        }
+        info
+    }
+
+    /// Set a single detail from a header comment or sidecar field.
+    fn set(&mut self, key: &str, value: &Value) {
+        match (key, value) {
+            ("name", Value::String(name)) if !name.is_empty() => self.name = name.clone(),
+            ("icon", Value::String(icon)) => self.icon = icon.chars().next(),
+            ("confirm", Value::Bool(confirm)) => self.confirm = *confirm,
+            ("confirm", Value::String(confirm)) => self.confirm = confirm == "yes" || confirm == "true",
+            _ => {}
+        }
+    }
+}
+
+/// Whether the file at `path` is a regular file that can be executed.
+fn is_executable(path: &Path) -> bool {
+    match fs::metadata(path) {
+        Ok(metadata) => metadata.is_file() && metadata.permissions().mode() & 0o111 != 0,
+        Err(_) => false
+    }
+}
+
+/// A view that shows a menu of the executable scripts in a folder, and runs
+/// the selected one with a `CommandView`.
+///
+/// A script can set how it is shown with comments near its top:
+/// ```sh
+/// #!/bin/sh
+/// # piscreen-name: Restart network
+/// # piscreen-icon: *
+/// # piscreen-confirm: yes
+/// ```
+/// or with a sidecar JSON file next to it, named after the script with `.json`
+/// added, e.g. `{ "name": "Restart network", "icon": "*", "confirm": true }`.
+/// Scripts that need confirmation ask before running.
+pub struct ScriptView {
+    path: PathBuf,
+    menu: MenuView
+}
+
+impl ScriptView {
+    /// Create a new script view from a folder path.
+    pub fn new(dir: &str) -> ScriptView {
+        ScriptView {
+            path: PathBuf::from(dir),
+            menu: MenuView::new()
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self.path.file_name() {
+            Some(name) => name.to_string_lossy().into_owned(),
+            None => self.path.to_string_lossy().into_owned()
+        }
+    }
+
+    /// Create the menu entry for a script.
+    fn entry(path: &Path) -> MenuEntry {
+        let info = ScriptInfo::read(path);
+        let program = path.to_string_lossy().into_owned();
+        let mut entry = MenuEntry::lazy(&info.name, move || CommandView::new(&program, &[]));
+        if info.confirm {
+            entry = entry.with_confirmation(&format!("Run {}?", info.name));
+        }
+        match info.icon {
+            Some(icon) => entry.with_icon(icon),
+            None => entry
+        }
+    }
+}
+
+impl View for ScriptView {
+    fn render(&mut self, disp: &mut Display) {
+        self.menu.render(disp);
+    }
+
+    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        self.menu.handle_buttons(buttons)
+    }
+
+    fn activate(&mut self) {
+        self.menu.set_name(self.get_name().as_ref());
+        let mut scripts: Vec<PathBuf> = match self.path.read_dir() {
+            Ok(entries) => entries.filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| is_executable(path))
+                .collect(),
+            Err(_) => vec![]
+        };
+        scripts.sort();
+        self.menu.set_entries(scripts.iter().map(|path| ScriptView::entry(path)).collect());
+    }
+
+    fn set_ancestors(&mut self, ancestors: &[String]) {
+        self.menu.set_ancestors(ancestors);
+    }
+
+    fn path(&self) -> Vec<String> {
+        self.menu.path()
+    }
+
+    fn open_path(&mut self, path: &[String]) -> usize {
+        self.menu.open_path(path)
+    }
+}
@@ -0,0 +1,156 @@
+use embedded_graphics::fonts::{Font,Font6x8};
+use embedded_graphics::coord::Coord;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rect;
+use embedded_graphics::Drawing;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::{
+    buttons::ButtonSet,
+    views::{step_decimals, ON, OFF},
+    View, ReturnState, ReturnStateEnum::*, Display
+};
+
+/// Left end of the slider track.
+const TRACK_LEFT: i32 = 6;
+/// Right end of the slider track.
+const TRACK_RIGHT: i32 = 121;
+
+/// A view with a horizontal slider for a value between a minimum and a
+/// maximum.
+///
+/// Left and right move the slider by the step, updating the shared value and
+/// calling the change callback straight away so the effect can be seen. A
+/// accepts the value, returning it to the parent view as `Text`; B puts back
+/// the value the slider was opened with.
+/// ```
+/// # use piscreen::views::SliderView;
+/// # use std::{rc::Rc, cell::Cell};
+/// let volume = Rc::new(Cell::new(70.0));
+/// SliderView::new("Volume", volume.clone(), 0.0, 100.0, 5.0)
+///     .with_unit("%")
+///     .with_on_change(|volume| println!("Volume is now {}", volume));
+/// ```
+pub struct SliderView {
+    name: String,
+    value: Rc<Cell<f64>>,
+    min: f64,
+    max: f64,
+    step: f64,
+    unit: String,
+    original: f64,
+    on_change: Option<Box<dyn FnMut(f64)>>
+}
+
+impl SliderView {
+    /// Create a slider for `value`, moving from `min` to `max` by `step`.
+    pub fn new(name: &str, value: Rc<Cell<f64>>, min: f64, max: f64, step: f64) -> SliderView {
+        let original = value.get();
+        SliderView {
+            name: name.to_owned(),
+            value,
+            min,
+            max: max.max(min),
+            step: step.abs(),
+            unit: String::new(),
+            original,
+            on_change: None
+        }
+    }
+
+    /// Show `unit` after the value, e.g. `%`.
+    pub fn with_unit(mut self, unit: &str) -> SliderView {
+        self.unit = unit.to_owned();
+        self
+    }
+
+    /// Call `on_change` with the new value whenever the slider moves.
+    pub fn with_on_change<F: FnMut(f64) + 'static>(mut self, on_change: F) -> SliderView {
+        self.on_change = Some(Box::new(on_change));
+        self
+    }
+
+    /// Set the shared value, calling the change callback if it changed.
+    fn set(&mut self, value: f64) {
+        if value != self.value.get() {
+            self.value.set(value);
+            if let Some(on_change) = &mut self.on_change {
+                on_change(value);
+            }
+        }
+    }
+
+    /// Move the slider by `steps` steps.
+    fn slide(&mut self, steps: f64) {
+        let value = (self.value.get() + steps * self.step).clamp(self.min, self.max);
+        self.set(value);
+    }
+
+    /// Format a value with as many decimal places as the step needs.
+    fn format(&self, value: f64) -> String {
+        format!("{:.*}", step_decimals(self.step), value)
+    }
+}
+
+impl View for SliderView {
+    fn render(&mut self, disp: &mut Display) {
+        let value = self.value.get();
+        disp.draw(Font6x8::render_str(&self.name)
+            .translate(Coord::new(3, 3))
+            .into_iter());
+        let label = format!("{}{}", self.format(value), self.unit);
+        disp.draw(Font6x8::render_str(&label)
+            .translate(Coord::new(124 - 6 * label.chars().count() as i32, 3))
+            .into_iter());
+        let fraction = match self.max > self.min {
+            true => ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0),
+            false => 0.0
+        };
+        let thumb = TRACK_LEFT + ((TRACK_RIGHT - TRACK_LEFT) as f64 * fraction).round() as i32;
+        disp.draw(Rect::new(Coord::new(TRACK_LEFT, 30), Coord::new(TRACK_RIGHT, 36))
+            .with_stroke(ON)
+            .into_iter());
+        disp.draw(Rect::new(Coord::new(TRACK_LEFT, 30), Coord::new(thumb, 36))
+            .with_fill(ON)
+            .into_iter());
+        disp.draw(Rect::new(Coord::new(thumb - 3, 25), Coord::new(thumb + 3, 41))
+            .with_stroke(ON)
+            .with_fill(OFF)
+            .into_iter());
+        let min = self.format(self.min);
+        let max = self.format(self.max);
+        disp.draw(Font6x8::render_str(&min)
+            .translate(Coord::new(3, 53))
+            .into_iter());
+        disp.draw(Font6x8::render_str(&max)
+            .translate(Coord::new(124 - 6 * max.chars().count() as i32, 53))
+            .into_iter());
+        disp.draw(Rect::new(Coord::new(0, 0), Coord::new(127, 63))
+            .with_stroke(ON)
+            .into_iter());
+    }
+
+    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        if buttons.left.was_pressed() {
+            self.slide(-1.0);
+        }
+        if buttons.right.was_pressed() {
+            self.slide(1.0);
+        }
+        if buttons.a.was_pressed() {
+            self.original = self.value.get();
+            return Some(Text(self.format(self.original)))
+        }
+        if buttons.b.was_pressed() {
+            self.set(self.original);
+            return Some(Pop)
+        }
+        None
+    }
+
+    fn activate(&mut self) {
+        self.original = self.value.get();
+    }
+}
@@ -0,0 +1,254 @@
+use embedded_graphics::fonts::{Font,Font6x8};
+use embedded_graphics::coord::Coord;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Line;
+use embedded_graphics::Drawing;
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::{
+    buttons::ButtonSet,
+    views::{text::wrap, ON},
+    View, ReturnState, ReturnStateEnum::*, Display
+};
+
+/// Number of output lines shown on the screen.
+const LINES_SHOWN: usize = 6;
+/// Default number of wrapped lines kept in the scrollback.
+const SCROLLBACK: usize = 1000;
+
+/// Send each line read from a pipe over a channel, on another thread.
+///
+/// Output that is not valid UTF-8 is decoded lossily, and the `\r` of CRLF
+/// line endings is dropped.
+fn forward_lines<R: Read + Send + 'static>(pipe: Option<R>, sender: Sender<String>) {
+    if let Some(pipe) = pipe {
+        thread::spawn(move || {
+            let mut reader = BufReader::new(pipe);
+            let mut line = vec![];
+            loop {
+                line.clear();
+                match reader.read_until(b'\n', &mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        let line = String::from_utf8_lossy(&line);
+                        let line = line.trim_end_matches('\n').trim_end_matches('\r');
+                        if sender.send(line.to_owned()).is_err() { break }
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// A view that runs a program and shows its output as it arrives.
+///
+/// The view follows new output until it is scrolled with up/down or paused
+/// with A; A resumes following. B asks for confirmation before stopping the
+/// program and every process it started, and leaves the view once it has
+/// exited.
+/// ```
+/// # use piscreen::views::StreamView;
+/// StreamView::new("apt-get", &["upgrade", "-y"])
+///     .with_scrollback(200);
+/// ```
+pub struct StreamView {
+    program: String,
+    args: Vec<String>,
+    scrollback: usize,
+    child: Option<Child>,
+    receiver: Option<Receiver<String>>,
+    lines: VecDeque<String>,
+    status: Option<String>,
+    offset: usize,
+    following: bool,
+    confirming: bool
+}
+
+impl StreamView {
+    /// Create a view that runs `program` with `args`.
+    pub fn new(program: &str, args: &[&str]) -> StreamView {
+        StreamView {
+            program: program.to_owned(),
+            args: args.iter().map(|arg| (*arg).to_owned()).collect(),
+            scrollback: SCROLLBACK,
+            child: None,
+            receiver: None,
+            lines: VecDeque::new(),
+            status: None,
+            offset: 0,
+            following: true,
+            confirming: false
+        }
+    }
+
+    /// Keep at most `lines` wrapped lines of output.
+    pub fn with_scrollback(mut self, lines: usize) -> StreamView {
+        self.scrollback = std::cmp::max(lines, LINES_SHOWN);
+        self
+    }
+
+    /// Whether the program is still running.
+    pub fn is_running(&self) -> bool {
+        self.child.is_some()
+    }
+
+    /// Add a line of output, dropping the oldest lines past the scrollback.
+    fn push_line(&mut self, line: &str) {
+        for line in wrap(line) {
+            self.lines.push_back(line);
+        }
+        while self.lines.len() > self.scrollback {
+            self.lines.pop_front();
+            self.offset = self.offset.saturating_sub(1);
+        }
+    }
+
+    /// The largest offset that still fills the screen.
+    fn max_offset(&self) -> usize {
+        self.lines.len().saturating_sub(LINES_SHOWN)
+    }
+
+    /// Read any new output and check whether the program has exited.
+    fn poll(&mut self) {
+        let received: Vec<String> = match &self.receiver {
+            Some(receiver) => receiver.try_iter().collect(),
+            None => vec![]
+        };
+        for line in received {
+            self.push_line(&line);
+        }
+        let exited = match &mut self.child {
+            Some(child) => child.try_wait().ok().flatten(),
+            None => None
+        };
+        if let Some(status) = exited {
+            self.child = None;
+            self.confirming = false;
+            self.status = Some(match status.code() {
+                Some(code) => format!("exit {}", code),
+                None => "killed".to_owned()
+            });
+        }
+        if self.following {
+            self.offset = self.max_offset();
+        }
+    }
+
+    /// Kill the program and any processes it started if it is running, and
+    /// wait for it to exit.
+    fn kill(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            // The program leads its own process group, so this reaches its children too.
+            unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL); }
+            let _ = child.wait();
+            self.status = Some("stopped".to_owned());
+        }
+        self.confirming = false;
+    }
+}
+
+impl View for StreamView {
+    fn render(&mut self, disp: &mut Display) {
+        for (i, line) in self.lines.iter().skip(self.offset).take(LINES_SHOWN).enumerate() {
+            disp.draw(Font6x8::render_str(line)
+                .translate(Coord::new(3, 1 + 9 * i as i32))
+                .into_iter());
+        }
+        disp.draw(Line::new(Coord::new(0, 54), Coord::new(127, 54))
+            .with_stroke(ON)
+            .into_iter());
+        let footer = match (&self.status, self.confirming) {
+            (_, true) => "Stop? A:yes B:no".to_owned(),
+            (Some(status), false) => format!("[{}]", status),
+            (None, false) => match self.following {
+                true => "[running]".to_owned(),
+                false => "[paused]".to_owned()
+            }
+        };
+        disp.draw(Font6x8::render_str(&footer)
+            .translate(Coord::new(3, 56))
+            .into_iter());
+    }
+
+    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        self.poll();
+        if self.confirming {
+            if buttons.a.was_pressed() {
+                self.kill();
+            }
+            if buttons.b.was_pressed() {
+                self.confirming = false;
+            }
+            return None
+        }
+        if buttons.up.was_pressed() {
+            self.following = false;
+            self.offset = self.offset.saturating_sub(1);
+        }
+        if buttons.down.was_pressed() {
+            self.offset = std::cmp::min(self.offset + 1, self.max_offset());
+            self.following = self.offset == self.max_offset();
+        }
+        if buttons.left.was_pressed() {
+            self.following = false;
+            self.offset = 0;
+        }
+        if buttons.right.was_pressed() {
+            self.following = true;
+            self.offset = self.max_offset();
+        }
+        if buttons.a.was_pressed() {
+            self.following = !self.following;
+            if self.following {
+                self.offset = self.max_offset();
+            }
+        }
+        if buttons.b.was_pressed() {
+            match self.is_running() {
+                true => self.confirming = true,
+                false => return Some(Pop)
+            }
+        }
+        None
+    }
+
+    fn activate(&mut self) {
+        self.kill();
+        self.lines.clear();
+        self.status = None;
+        self.offset = 0;
+        self.following = true;
+        let spawned = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .process_group(0)
+            .spawn();
+        match spawned {
+            Ok(mut child) => {
+                let (sender, receiver) = mpsc::channel();
+                forward_lines(child.stdout.take(), sender.clone());
+                forward_lines(child.stderr.take(), sender);
+                self.child = Some(child);
+                self.receiver = Some(receiver);
+            }
+            Err(e) => {
+                self.push_line(&format!("Could not run {}: {}", self.program, e));
+                self.status = Some("error".to_owned());
+            }
+        }
+    }
+}
+
+impl Drop for StreamView {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt::Display as Show;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+
+use crate::{
+    buttons::ButtonSet,
+    views::{TextView, ProgressView},
+    View, ReturnState, ReturnStateEnum::*, Display
+};
+
+/// A task run by a `TaskView`, with its result turned into strings.
+type Task = dyn Fn(&Progress) -> Result<String, String> + Send + Sync;
+
+/// A message sent from a task's thread to its view.
+enum TaskMessage {
+    Fraction(f32),
+    Status(String),
+    Done(Result<String, String>)
+}
+
+/// Handle given to a task for reporting its progress.
+pub struct Progress {
+    sender: Sender<TaskMessage>,
+    cancelled: Arc<AtomicBool>
+}
+
+impl Progress {
+    /// Report how much of the task is done, from 0.0 to 1.0.
+    pub fn set_fraction(&self, fraction: f32) {
+        let _ = self.sender.send(TaskMessage::Fraction(fraction.clamp(0.0, 1.0)));
+    }
+
+    /// Report what the task is doing.
+    pub fn set_status(&self, status: &str) {
+        let _ = self.sender.send(TaskMessage::Status(status.to_owned()));
+    }
+
+    /// Whether the task has been cancelled, and should stop as soon as it can.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+enum TaskState {
+    Idle,
+    Running {
+        receiver: Receiver<TaskMessage>,
+        cancelled: Arc<AtomicBool>,
+        progress: ProgressView
+    },
+    Finished(TextView)
+}
+
+/// A view that runs a task on a background thread when activated, showing
+/// its progress while it runs and then its result.
+///
+/// The task reports through a `Progress`, shown in a `ProgressView`: a
+/// progress bar once it sets a fraction, and a spinner until then. Pressing B
+/// cancels the task and leaves; the task should check
+/// `Progress::is_cancelled` and stop early.
+/// When it finishes, its message or error is shown, or the view leaves if the
+/// message is empty.
+/// ```
+/// # use piscreen::views::TaskView;
+/// TaskView::new("Copying", |progress| {
+///     for i in 0..10 {
+///         if progress.is_cancelled() {
+///             return Err("cancelled");
+///         }
+///         progress.set_status(&format!("File {} of 10", i + 1));
+///         progress.set_fraction(i as f32 / 10.0);
+///     }
+///     Ok("Copied 10 files")
+/// });
+/// ```
+pub struct TaskView {
+    name: String,
+    task: Arc<Task>,
+    state: TaskState
+}
+
+impl TaskView {
+    /// Create a view that runs `task`, showing `name` while it runs.
+    pub fn new<T, E, F>(name: &str, task: F) -> TaskView
+    where T: Show, E: Show, F: Fn(&Progress) -> Result<T, E> + Send + Sync + 'static {
+        TaskView {
+            name: name.to_owned(),
+            task: Arc::new(move |progress: &Progress| {
+                task(progress).map(|message| message.to_string()).map_err(|e| e.to_string())
+            }),
+            state: TaskState::Idle
+        }
+    }
+
+    /// Whether the task is currently running.
+    pub fn is_running(&self) -> bool {
+        matches!(self.state, TaskState::Running { .. })
+    }
+
+    /// Cancel the task if it is running. Its result is ignored.
+    fn cancel(&mut self) {
+        if let TaskState::Running { cancelled, .. } = &self.state {
+            cancelled.store(true, Ordering::Relaxed);
+            self.state = TaskState::Idle;
+        }
+    }
+
+    /// Apply the messages sent by the task since the last poll.
+    fn poll(&mut self) {
+        let mut result = None;
+        if let TaskState::Running { receiver, progress, .. } = &mut self.state {
+            let handle = progress.handle();
+            loop {
+                match receiver.try_recv() {
+                    Ok(TaskMessage::Fraction(f)) => handle.set_fraction(f),
+                    Ok(TaskMessage::Status(s)) => handle.set_status(&s),
+                    Ok(TaskMessage::Done(r)) => result = Some(r),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        result.get_or_insert_with(|| Err("the task stopped unexpectedly".to_owned()));
+                        break
+                    }
+                }
+            }
+        }
+        self.state = match result {
+            Some(Ok(message)) if message.is_empty() => TaskState::Idle,
+            Some(Ok(message)) => TaskState::Finished(TextView::new(&message)),
+            Some(Err(e)) => TaskState::Finished(TextView::new(&format!("Error: {}", e))),
+            None => return
+        };
+    }
+}
+
+impl View for TaskView {
+    fn render(&mut self, disp: &mut Display) {
+        match &mut self.state {
+            TaskState::Running { progress, .. } => progress.render(disp),
+            TaskState::Finished(text) => text.render(disp),
+            TaskState::Idle => {}
+        }
+    }
+
+    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+        self.poll();
+        match &mut self.state {
+            TaskState::Finished(text) => text.handle_buttons(buttons),
+            TaskState::Running { .. } => {
+                if buttons.b.was_pressed() {
+                    self.cancel();
+                    return Some(Pop)
+                }
+                None
+            }
+            TaskState::Idle => Some(Pop)
+        }
+    }
+
+    fn activate(&mut self) {
+        self.cancel();
+        let (sender, receiver) = mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let progress = Progress {
+            sender,
+            cancelled: cancelled.clone()
+        };
+        let task = self.task.clone();
+        thread::spawn(move || {
+            let result = task(&progress);
+            let _ = progress.sender.send(TaskMessage::Done(result));
+        });
+        let mut progress = ProgressView::new(&self.name);
+        progress.activate();
+        self.state = TaskState::Running {
+            receiver,
+            cancelled,
+            progress
+        };
+    }
+}
+
+impl Drop for TaskView {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
@@ -15,36 +15,55 @@ use embedded_graphics::{
 const SCROLL_AMOUNT: u16 = 7;
 const SCROLL_AMOUNT_HOLD: u16 = 4;
 
-/// A view that renders wrapped, scrolling text.
-pub struct TextView { text: String, offset: u16 }
+/// Maximum number of characters on a line of wrapped text.
+const LINE_CHARS: usize = 20;
 
-impl TextView {
-    pub fn new(text: &str) -> TextView {
-        TextView { text: text.to_owned(), offset: 0 }
-    }
-
-    /// Wrap the text in the view over several lines.
-    pub fn get_lines(&self) -> Vec<String> {
-        let mut lines: Vec<String> = Vec::new();
+/// Wrap text to the width of the screen.
+/// Newlines in the text always start a new line, and words too long to fit
+/// on a line are broken up.
+pub(crate) fn wrap(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for paragraph in text.split('\n') {
         let mut next: String = String::new();
-        for word in self.text.split_ascii_whitespace() {
-            if next.len() + word.len() < 20 {
+        let words = paragraph.split_ascii_whitespace().flat_map(|word| {
+            let chars: Vec<char> = word.chars().collect();
+            chars.chunks(LINE_CHARS).map(|chunk| chunk.iter().collect::<String>()).collect::<Vec<_>>()
+        });
+        for word in words {
+            if next.len() + word.len() < LINE_CHARS {
                 next.push(' ');
                 next.push_str(word.as_ref());
             } else {
-                lines.push(next.trim().to_owned());
+                if !next.is_empty() {
+                    lines.push(next.trim().to_owned());
+                }
                 next.clear();
                 next.push_str(word.as_ref());
             }
         }
         lines.push(next.trim().to_owned());
-        lines
+    }
+    lines
+}
+
+/// A view that renders wrapped, scrolling text.
+pub struct TextView { text: String, offset: u16 }
+
+impl TextView {
+    pub fn new(text: &str) -> TextView {
+        TextView { text: text.to_owned(), offset: 0 }
+    }
+
+    /// Wrap the text in the view over several lines.
+    /// Newlines in the text always start a new line.
+    pub fn get_lines(&self) -> Vec<String> {
+        wrap(&self.text)
     }
 
     /// Get the maximum vertical offset of the contained text when rendered to
     /// the screen.
     fn get_max_offset(&self) -> u16 {
-        return (self.get_lines().len()) as u16 * 9 + 1 - 60;
+        ((self.get_lines().len()) as u16 * 9 + 1).saturating_sub(60)
     }
 }
 
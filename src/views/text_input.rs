@@ -1,14 +1,11 @@
 use embedded_graphics::fonts::{Font,Font6x8};
 use embedded_graphics::coord::Coord;
 use embedded_graphics::prelude::*;
-use embedded_graphics::image::Image1BPP;
 use embedded_graphics::pixelcolor::PixelColorU8;
 use embedded_graphics::primitives::Rect;
 use embedded_graphics::primitives::Line;
 use embedded_graphics::Drawing;
 
-use std::time::SystemTime;
-
 use crate::{
     buttons::ButtonSet,
     View, ReturnState, ReturnStateEnum::*, Display
@@ -20,7 +17,8 @@ enum Direction { Up, Down, Left, Right }
 use TextInputMode::*;
 use Direction::*;
 
-struct InputTree {
+/// Picks a single character by walking a tree of character groups with the d-pad.
+pub(crate) struct InputTree {
     caps: bool,
     group: &'static str,
 }
@@ -66,18 +64,18 @@ impl InputTree {
         }
     }
 
-    fn new() -> InputTree {
+    pub(crate) fn new() -> InputTree {
         InputTree {
             caps: false,
             group: "",
         }
     }
 
-    fn reset(&mut self) {
+    pub(crate) fn reset(&mut self) {
         self.group = "";
     }
     
-    fn render(&self, disp: &mut Display) {
+    pub(crate) fn render(&self, disp: &mut Display) {
 
         let up: String;
         let down: String;
@@ -96,7 +94,7 @@ impl InputTree {
             left = InputTree::get_group(self.group, Left).to_lowercase();
         }
 
-        if up.len() > 0 {
+        if !up.is_empty() {
             disp.draw(Font6x8::render_str(up.as_str())
                 .translate(Coord::new(64 - (up.len() as i32 * 6) / 2, 24 - 16))
                 .into_iter());
@@ -104,7 +102,7 @@ impl InputTree {
                 .with_stroke(Some(PixelColorU8(1)))
                 .into_iter())
         }
-        if down.len() > 0 {
+        if !down.is_empty() {
             disp.draw(Font6x8::render_str(down.as_str())
                 .translate(Coord::new(64 - (down.len() as i32 * 6) / 2, 24 + 8))
                 .into_iter());
@@ -112,7 +110,7 @@ impl InputTree {
                 .with_stroke(Some(PixelColorU8(1)))
                 .into_iter())
         }
-        if left.len() > 0 {
+        if !left.is_empty() {
             disp.draw(Font6x8::render_str(left.as_str())
                 .translate(Coord::new(64 - 8 - left.len() as i32 * 6, 24 - 4))
                 .into_iter());
@@ -120,7 +118,7 @@ impl InputTree {
                 .with_stroke(Some(PixelColorU8(1)))
                 .into_iter())
         }
-        if right.len() > 0 {
+        if !right.is_empty() {
             disp.draw(Font6x8::render_str(right.as_str())
                 .translate(Coord::new(64 + 8, 24 - 4))
                 .into_iter());
@@ -130,7 +128,7 @@ impl InputTree {
         }
     }
 
-    fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
+    pub(crate) fn handle_buttons(&mut self, buttons: &mut ButtonSet) -> ReturnState {
         if buttons.b.was_pressed() {
             return Some(Pop);
         }
@@ -147,8 +145,6 @@ impl InputTree {
             else { return None };
         
         self.group = InputTree::get_group(self.group, pressed);
-
-        println!("{}", self.group);
         match self.group.len() {
             0 => Some(Text(" ".to_owned())),
             1 => Some(Text(self.group.to_owned())),
@@ -196,6 +192,10 @@ impl TextInputView {
     }
 }
 
+impl Default for TextInputView {
+    fn default() -> TextInputView { TextInputView::new() }
+}
+
 impl View for TextInputView {
     fn render(&mut self, disp: &mut Display) {
         self.render_text(disp);
@@ -212,10 +212,8 @@ impl View for TextInputView {
                     self.input_tree.reset();
                     self.mode = Entering
                 }
-                if buttons.left.was_pressed() {
-                    if self.selected_char > 0 {
-                        self.selected_char -= 1;
-                    }
+                if buttons.left.was_pressed() && self.selected_char > 0 {
+                    self.selected_char -= 1;
                 }
 
                 if buttons.right.was_pressed() {
@@ -246,8 +244,8 @@ impl View for TextInputView {
                                 out.push(char::from(*byte))
                             }
                         }
-                        if !out.ends_with(" ") {
-                            out.push_str(" ")
+                        if !out.ends_with(' ') {
+                            out.push(' ')
                         }
                         self.text = out;
                         None
@@ -0,0 +1,79 @@
+use std::ffi::{CString, OsStr};
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+/// Size of the buffer that inotify events are read into.
+const EVENT_BUFFER_SIZE: usize = 4096;
+
+/// Watches a single file for changes with inotify.
+///
+/// The directory containing the file is watched rather than the file itself,
+/// so that changes made by editors that replace the file are still seen.
+pub(crate) struct FileWatcher {
+    fd: libc::c_int,
+    name: Vec<u8>
+}
+
+impl FileWatcher {
+    /// Start watching the file at `path`.
+    pub(crate) fn new(path: &Path) -> io::Result<FileWatcher> {
+        let dir = match path.parent() {
+            Some(dir) if dir.as_os_str().is_empty() => Path::new("."),
+            Some(dir) => dir,
+            None => Path::new("/")
+        };
+        let name = path.file_name().unwrap_or_else(|| OsStr::new("")).as_bytes().to_vec();
+        let dir = CString::new(dir.as_os_str().as_bytes())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let fd = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error())
+        }
+        let watcher = FileWatcher { fd, name };
+        // Only finished writes, so that a file is not read while it is half-written.
+        let mask = libc::IN_CLOSE_WRITE | libc::IN_MOVED_TO;
+        if unsafe { libc::inotify_add_watch(fd, dir.as_ptr(), mask) } < 0 {
+            return Err(io::Error::last_os_error())
+        }
+        Ok(watcher)
+    }
+
+    /// Return whether the file has changed since this was last called.
+    /// Never blocks.
+    pub(crate) fn changed(&mut self) -> bool {
+        let mut changed = false;
+        let mut buffer = [0u8; EVENT_BUFFER_SIZE];
+        loop {
+            let read = unsafe {
+                libc::read(self.fd, buffer.as_mut_ptr() as *mut libc::c_void, buffer.len())
+            };
+            if read <= 0 {
+                return changed
+            }
+            let header = std::mem::size_of::<libc::inotify_event>();
+            let mut offset = 0;
+            while offset + header <= read as usize {
+                let event = unsafe {
+                    std::ptr::read_unaligned(buffer.as_ptr().add(offset) as *const libc::inotify_event)
+                };
+                let name = &buffer[offset + header..offset + header + event.len as usize];
+                let name = match name.iter().position(|&b| b == 0) {
+                    Some(end) => &name[..end],
+                    None => name
+                };
+                if name == self.name.as_slice() {
+                    changed = true;
+                }
+                offset += header + event.len as usize;
+            }
+        }
+    }
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.fd); }
+    }
+}